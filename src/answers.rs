@@ -0,0 +1,96 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::error::AocError;
+
+/// The answers file in the source tree, next to the inputs directory.
+pub fn answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+/// Recorded answers for the real puzzle inputs, stored as `[YEAR.dayN]` tables with
+/// `part1`/`part2` keys.
+#[derive(Default)]
+pub struct Answers(BTreeMap<(u16, usize), [Option<String>; 2]>);
+
+impl Answers {
+    /// Loads the answers file, starting out empty if it doesn't exist yet.
+    pub fn load() -> Result<Self, AocError> {
+        let contents = match fs::read_to_string(answers_path()) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(AocError::Answers(e.to_string())),
+        };
+        let table: toml::Table = contents
+            .parse()
+            .map_err(|e: toml::de::Error| AocError::Answers(e.to_string()))?;
+
+        let mut answers = Self::default();
+        for (year_key, days) in table {
+            let year: u16 = year_key
+                .parse()
+                .map_err(|_| AocError::Answers(format!("Expected a year, found `{}`", year_key)))?;
+            let days = days
+                .as_table()
+                .ok_or_else(|| AocError::Answers(format!("{} must be a table", year_key)))?;
+
+            for (key, parts) in days {
+                let day: usize = key
+                    .strip_prefix("day")
+                    .and_then(|d| d.parse().ok())
+                    .ok_or_else(|| {
+                        AocError::Answers(format!("Expected `dayN`, found `{}`", key))
+                    })?;
+
+                for part in 1..=2 {
+                    if let Some(answer) = parts.get(format!("part{}", part)) {
+                        let answer = answer.as_str().ok_or_else(|| {
+                            AocError::Answers(format!(
+                                "Answers for {}.{} must be strings",
+                                year, key
+                            ))
+                        })?;
+                        answers.set(year, day, part, answer.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn save(&self) -> Result<(), AocError> {
+        let mut contents = String::new();
+        for ((year, day), parts) in &self.0 {
+            if !contents.is_empty() {
+                contents.push('\n');
+            }
+            let _ = writeln!(contents, "[{}.day{}]", year, day);
+            for (i, answer) in parts.iter().enumerate() {
+                if let Some(answer) = answer {
+                    let _ = writeln!(
+                        contents,
+                        "part{} = {}",
+                        i + 1,
+                        toml::Value::from(answer.as_str())
+                    );
+                }
+            }
+        }
+
+        fs::write(answers_path(), contents).map_err(|e| AocError::Answers(e.to_string()))
+    }
+
+    pub fn get(&self, year: u16, day: usize, part: usize) -> Option<&str> {
+        self.0.get(&(year, day))?[part - 1].as_deref()
+    }
+
+    pub fn set(&mut self, year: u16, day: usize, part: usize, answer: String) {
+        self.0.entry((year, day)).or_default()[part - 1] = Some(answer);
+    }
+}
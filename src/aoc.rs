@@ -1,13 +1,417 @@
-use std::{io::{BufReader, BufRead}, fs::File};
+use std::{
+    any::Any,
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    iter,
+    path::{Path, PathBuf},
+    str::{FromStr, Lines},
+    sync::atomic::{AtomicU8, Ordering},
+};
 
-#[allow(dead_code)]
-pub fn lines_from_test(data: &str) -> Vec<String> {
-    data.lines().map(|l| l.to_string()).collect()
+use rand::rngs::StdRng;
+
+use crate::error::AocError;
+
+/// How far along a part's solution is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    /// Not written yet. The runner skips the part and reports it as not implemented.
+    Stub,
+    /// Runs, but is known to give the wrong answer.
+    Wrong,
+}
+
+impl Display for PartStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Stub => "stub",
+            PartStatus::Wrong => "wrong",
+        })
+    }
+}
+
+/// What a day is about, as listed by `aoc2022 list`.
+#[derive(Clone, Copy, Debug)]
+pub struct Metadata {
+    /// The puzzle's title on the Advent of Code site.
+    pub title: &'static str,
+    pub parts: [PartStatus; 2],
+    /// What the puzzle involves, such as `grid` or `graph`.
+    pub tags: &'static [&'static str],
+}
+
+/// A part's answer. Integers and strings convert into answers with `into()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Several lines of text drawing the answer, such as letters lit up on a screen.
+    Picture(String),
+    NotImplemented,
+}
+
+impl Answer {
+    pub fn is_picture(&self) -> bool {
+        matches!(self, Answer::Picture(_))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::Text(text) | Answer::Picture(text) => f.write_str(text),
+            Answer::NotImplemented => f.write_str("NOT IMPLEMENTED"),
+        }
+    }
+}
+
+macro_rules! integer_answers {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_answers!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// A day's puzzle solution, split into a parse stage and the two parts
+/// operating on the parsed input.
+pub trait Solution {
+    type Input: 'static;
+
+    const METADATA: Metadata;
+
+    fn parse(&self, input: &Input) -> Self::Input;
+    fn part1(&self, input: &Self::Input) -> Answer;
+    fn part2(&self, input: &Self::Input) -> Answer;
+
+    /// Generates a random puzzle input of about `size` items (elves, lines, monkeys...),
+    /// for scale testing and fuzzing the parser. Days without a generator return `None`.
+    fn generate(&self, _rng: &mut StdRng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+/// Type-erased [`Solution`], so days with different inputs can share a registry.
+pub trait AnySolution: Sync {
+    fn parse(&self, input: &Input) -> Box<dyn Any>;
+    fn part1(&self, input: &dyn Any) -> Answer;
+    fn part2(&self, input: &dyn Any) -> Answer;
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String>;
+    fn metadata(&self) -> Metadata;
+}
+
+impl<S: Solution + Sync> AnySolution for S {
+    fn parse(&self, input: &Input) -> Box<dyn Any> {
+        Box::new(Solution::parse(self, input))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Answer {
+        Solution::part1(self, input.downcast_ref().expect("Input of another day"))
+    }
+
+    fn part2(&self, input: &dyn Any) -> Answer {
+        Solution::part2(self, input.downcast_ref().expect("Input of another day"))
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Solution::generate(self, rng, size)
+    }
+
+    fn metadata(&self) -> Metadata {
+        S::METADATA
+    }
+}
+
+/// How much diagnostic output to print, from `-q` for none to `-vv` for everything.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,
+    /// Status messages from the runner, such as files being created.
+    Normal,
+    /// Visual output from solvers, such as grids and pictures.
+    Verbose,
+    /// Everything solvers have to say about how they got to their answers.
+    Debug,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Whether output at `level` should be printed.
+pub fn enabled(level: Verbosity) -> bool {
+    VERBOSITY.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Prints a status message to stderr unless `-q` was given.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::aoc::enabled($crate::aoc::Verbosity::Normal) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Prints visual output, such as a grid, to stderr when `-v` was given. The arguments
+/// aren't evaluated otherwise, so they don't count towards a day's timings.
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::aoc::enabled($crate::aoc::Verbosity::Verbose) {
+            eprintln!($($arg)*);
+        }
+    };
 }
 
-pub fn lines_from_file(path: &str) -> Vec<String> {
-    BufReader::new(File::open(path).expect("Failed to find file"))
-        .lines()
-        .map(|l| l.unwrap())
-        .collect()
+/// Prints debug output to stderr when `-vv` was given.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::aoc::enabled($crate::aoc::Verbosity::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// A puzzle input, read once into a single buffer. CRLF line endings, a leading byte
+/// order mark and trailing blank lines are normalised away, and the accessors borrow from
+/// the buffer.
+pub struct Input(String);
+
+impl Input {
+    pub fn new(text: impl Into<String>) -> Self {
+        let mut text = text.into();
+
+        if text.starts_with('\u{feff}') {
+            text.drain(..'\u{feff}'.len_utf8());
+        }
+        if text.contains('\r') {
+            text = text.replace("\r\n", "\n");
+        }
+        // Keep trailing whitespace on the last line with content, it can be significant
+        let content_end = text.trim_end().len();
+        let end = text[content_end..]
+            .find('\n')
+            .map_or(text.len(), |i| content_end + i);
+        text.truncate(end);
+
+        Self(text)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, AocError> {
+        fs::read_to_string(path)
+            .map(Self::new)
+            .map_err(|source| AocError::Input {
+                path: path.display().to_string(),
+                source,
+            })
+    }
+
+    pub fn from_stdin() -> Result<Self, AocError> {
+        let mut text = String::new();
+        io::stdin()
+            .lock()
+            .read_to_string(&mut text)
+            .map(|_| Self::new(text))
+            .map_err(|source| AocError::Input {
+                path: "<stdin>".to_string(),
+                source,
+            })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn lines(&self) -> Lines<'_> {
+        self.0.lines()
+    }
+
+    /// The lines copied out of the buffer, for days that keep them as their parsed input.
+    pub fn owned_lines(&self) -> Vec<String> {
+        self.lines().map(|l| l.to_string()).collect()
+    }
+
+    /// Groups of lines separated by blank lines, such as one elf's calories. Runs of blank
+    /// or whitespace-only lines count as a single separator.
+    pub fn blocks(&self) -> impl Iterator<Item = &str> {
+        let text = self.0.as_str();
+        let mut lines = text
+            .split_inclusive('\n')
+            .scan(0, |offset, line| {
+                let start = *offset;
+                *offset += line.len();
+                Some((start, line))
+            })
+            .peekable();
+        let is_blank = |(_, line): &(usize, &str)| line.trim().is_empty();
+
+        iter::from_fn(move || {
+            while lines.next_if(is_blank).is_some() {}
+            let (start, first) = lines.next()?;
+            let mut end = start + first.trim_end_matches('\n').len();
+            while let Some((line_start, line)) = lines.next_if(|line| !is_blank(line)) {
+                end = line_start + line.trim_end_matches('\n').len();
+            }
+
+            Some(&text[start..end])
+        })
+    }
+
+    pub fn grid(&self) -> Grid<'_> {
+        Grid(self.lines().map(|l| l.as_bytes()).collect())
+    }
+
+    pub fn char_grid(&self) -> Vec<Vec<char>> {
+        self.lines().map(|l| l.chars().collect()).collect()
+    }
+
+    /// Every integer in the input, see [`ints`].
+    pub fn ints<T: FromStr>(&self) -> impl Iterator<Item = T> + '_ {
+        ints(&self.0)
+    }
+}
+
+/// The rows of an input as bytes, indexed by `(x, y)` from the top left.
+pub struct Grid<'a>(Vec<&'a [u8]>);
+
+impl<'a> Grid<'a> {
+    pub fn width(&self) -> usize {
+        self.0.first().map_or(0, |row| row.len())
+    }
+
+    pub fn height(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        self.0.get(y)?.get(x).copied()
+    }
+
+    pub fn rows(&self) -> &[&'a [u8]] {
+        &self.0
+    }
+}
+
+/// Every integer in `text` that fits in `T`, skipping whatever is between them. A `-`
+/// right before a number makes it negative, unless it follows a digit as in `2-4`.
+pub fn ints<T: FromStr>(text: &str) -> impl Iterator<Item = T> + '_ {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let start = i;
+            let negative = bytes[i] == b'-'
+                && (i == 0 || !bytes[i - 1].is_ascii_digit())
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+
+            i += 1;
+            if negative || bytes[start].is_ascii_digit() {
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                if let Ok(n) = text[start..i].parse() {
+                    return Some(n);
+                }
+            }
+        }
+        None
+    })
+}
+
+/// Environment variable overriding the directory puzzle inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The `inputs` directory in the source tree, used when `AOC_INPUT_DIR` isn't set.
+pub fn manifest_input_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs")
+}
+
+/// Finds `relative_path` in `AOC_INPUT_DIR` if set, or else in the source tree's `inputs`
+/// directory, so inputs resolve regardless of the working directory.
+pub fn find_input(relative_path: &Path) -> Result<PathBuf, AocError> {
+    let dir = env::var_os(INPUT_DIR_VAR).map_or_else(manifest_input_dir, PathBuf::from);
+    let path = dir.join(relative_path);
+
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(AocError::InputNotFound(path))
+    }
+}
+
+#[cfg(test)]
+mod input_tests {
+    use super::{ints, Input};
+
+    #[test]
+    fn test_normalisation() {
+        let input = Input::new("\u{feff}a \r\nb\r\n\r\n  \n");
+        assert_eq!("a \nb", input.as_str());
+
+        let input = Input::new("[D]    \n\n");
+        assert_eq!("[D]    ", input.as_str());
+
+        assert_eq!("", Input::new("\n\n").as_str());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = Input::new("1\n2\n\n3\n");
+        let blocks: Vec<_> = input.blocks().collect();
+        assert_eq!(vec!["1\n2", "3"], blocks);
+
+        let input = Input::new("\n1000\n\n\n2000\n  \n\t\n3000\n4000");
+        let blocks: Vec<_> = input.blocks().collect();
+        assert_eq!(vec!["1000", "2000", "3000\n4000"], blocks);
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = Input::new("ab\ncd\n");
+        let grid = input.grid();
+
+        assert_eq!((2, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(b'c'), grid.get(0, 1));
+        assert_eq!(None, grid.get(2, 0));
+        assert_eq!(vec![vec!['a', 'b'], vec!['c', 'd']], input.char_grid());
+    }
+
+    #[test]
+    fn test_ints() {
+        let sensor = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+        assert_eq!(
+            vec![2, -18, -2, 15],
+            ints::<i64>(sensor).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![2, 4, 6, 8], ints::<u32>("2-4,6-8").collect::<Vec<_>>());
+        assert_eq!(vec![3], ints::<u8>("move 300 to 3").collect::<Vec<_>>());
+    }
 }
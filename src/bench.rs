@@ -0,0 +1,150 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{aoc::PartStatus, error::AocError, runner, years::Year};
+
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort();
+
+        let n = samples.len() as f64;
+        let mean = samples.iter().map(|s| s.as_secs_f64()).sum::<f64>() / n;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n;
+
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Self {
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Stats for each stage of a day, in the order parse, part 1, part 2 and total. Stub parts
+/// aren't run, so they have no stats and don't count towards the total.
+pub struct DayBench {
+    pub day: usize,
+    pub stages: [(&'static str, Option<Stats>); 4],
+}
+
+/// Benchmarks a day, reading its input once up front so file IO isn't part of the timings.
+pub fn bench_day(
+    year: &Year,
+    day: usize,
+    iterations: u32,
+    warmup: u32,
+) -> Result<DayBench, AocError> {
+    let solution = year.solution(day)?;
+    let input = runner::read_input(year, day, None)?;
+    let stubs = solution.metadata().parts.map(|s| s == PartStatus::Stub);
+
+    let mut samples: [Vec<Duration>; 4] = Default::default();
+
+    for iteration in 0..warmup + iterations {
+        let now = Instant::now();
+        let parsed_input = black_box(solution.parse(black_box(&input)));
+        let parsed = now.elapsed();
+
+        let mut parts = [None; 2];
+        for (part, elapsed) in parts.iter_mut().enumerate() {
+            if stubs[part] {
+                continue;
+            }
+            let now = Instant::now();
+            black_box(match part {
+                0 => solution.part1(black_box(parsed_input.as_ref())),
+                _ => solution.part2(black_box(parsed_input.as_ref())),
+            });
+            *elapsed = Some(now.elapsed());
+        }
+
+        if iteration >= warmup {
+            let total = parsed + parts.iter().flatten().sum::<Duration>();
+            for (stage, sample) in
+                samples
+                    .iter_mut()
+                    .zip([Some(parsed), parts[0], parts[1], Some(total)])
+            {
+                stage.extend(sample);
+            }
+        }
+    }
+
+    let [parse, part1, part2, total] =
+        samples.map(|mut s| (!s.is_empty()).then(|| Stats::from_samples(&mut s)));
+
+    Ok(DayBench {
+        day,
+        stages: [
+            ("parse", parse),
+            ("part 1", part1),
+            ("part 2", part2),
+            ("total", total),
+        ],
+    })
+}
+
+pub fn print_bench(bench: &DayBench) {
+    println!("Day {}", bench.day);
+    println!(
+        "{:<6} | {:>10} | {:>10} | {:>10} | {:>10}",
+        "Stage", "Min", "Median", "Mean", "Stddev"
+    );
+    println!(
+        "{}-+-{}",
+        "-".repeat(6),
+        vec!["-".repeat(10); 4].join("-+-")
+    );
+    for (name, stats) in &bench.stages {
+        let Some(stats) = stats else {
+            println!("{:<6} | NOT IMPLEMENTED", name);
+            continue;
+        };
+        println!(
+            "{:<6} | {:>10} | {:>10} | {:>10} | {:>10}",
+            name,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.stddev)
+        );
+    }
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    #[test]
+    fn test_stats() {
+        let mut samples: Vec<_> = [4, 1, 3, 2].map(Duration::from_millis).to_vec();
+        let stats = Stats::from_samples(&mut samples);
+
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_micros(2500), stats.median);
+        assert_eq!(Duration::from_micros(2500), stats.mean);
+        assert_eq!(1118, stats.stddev.as_micros());
+    }
+}
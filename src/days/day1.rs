@@ -1,3 +1,7 @@
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
 /* Alternative versions
 fn calories_by_elf(lines: &[String]) -> Vec<u32> {
     lines
@@ -28,59 +32,66 @@ fn calories_by_elf(lines: &[String]) -> Vec<u32> {
 }
 */
 
-fn calories_by_elf(lines: &[String]) -> Vec<u32> {
+fn calories_by_elf(input: &Input) -> Vec<u32> {
     let mut calories: Vec<u32> = Vec::new();
 
-    for chunk in lines.split(|l| l.is_empty()) {
-        let chunk_u32s = chunk.iter().map(|l| l.parse::<u32>().unwrap());
+    for chunk in input.blocks() {
+        let chunk_u32s = chunk.lines().map(|l| l.parse::<u32>().unwrap());
         calories.push(chunk_u32s.sum())
     }
 
     calories
 }
 
-fn part1(lines: &[String]) -> u32 {
-    *calories_by_elf(lines).iter().max().unwrap()
+fn part1(calories: &[u32]) -> u32 {
+    *calories.iter().max().unwrap()
 }
 
-fn part2(lines: &[String]) -> u32 {
-    let mut calories = calories_by_elf(lines);
+fn part2(calories: &[u32]) -> u32 {
+    let mut calories = calories.to_vec();
     calories.sort_by(|a, b| b.cmp(a));
 
     calories.iter().take(3).sum()
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day1.txt");
-    let day1_result = part1(&lines);
-    let day2_result = part2(&lines);
+/// `size` elves carrying 1 to 15 snacks each.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(3))
+        .map(|_| {
+            let snacks: Vec<_> = (0..rng.gen_range(1..=15))
+                .map(|_| rng.gen_range(1000..=60000).to_string())
+                .collect();
+            snacks.join("\n")
+        })
+        .collect();
 
-    (day1_result.to_string(), day2_result.to_string())
+    elves.join("\n\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"1000
-2000
-3000
+pub struct Day1;
 
-4000
+impl Solution for Day1 {
+    type Input = Vec<u32>;
 
-5000
-6000
+    const METADATA: Metadata = Metadata {
+        title: "Calorie Counting",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["sorting"],
+    };
 
-7000
-8000
-9000
+    fn parse(&self, input: &Input) -> Self::Input {
+        calories_by_elf(input)
+    }
 
-10000
+    fn part1(&self, calories: &Self::Input) -> Answer {
+        part1(calories).into()
+    }
 
-",
-    );
+    fn part2(&self, calories: &Self::Input) -> Answer {
+        part2(calories).into()
+    }
 
-    let part1_result = part1(&lines);
-    let part2_result = part2(&lines);
-    assert_eq!(24000, part1_result);
-    assert_eq!(45000, part2_result);
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
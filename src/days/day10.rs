@@ -1,6 +1,30 @@
 use std::collections::HashMap;
 
-fn part1(instructions: &[String]) -> i32 {
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
+pub enum Instruction {
+    Addx(i32),
+    Noop,
+}
+
+fn parse_instructions(input: &Input) -> Vec<Instruction> {
+    input
+        .lines()
+        .map(|line| {
+            let args: Vec<_> = line.split_whitespace().collect();
+            match args.as_slice() {
+                ["addx", value] => Instruction::Addx(value.parse().unwrap()),
+                ["noop"] => Instruction::Noop,
+                _ => panic!("Invalid opcode"),
+            }
+        })
+        .collect()
+}
+
+/// Runs the CPU, returning the sum of the signal strengths and what it drew on the CRT.
+fn run(instructions: &[Instruction]) -> (i32, String) {
     let mut instruction_iter = instructions.iter();
 
     let mut x = 1;
@@ -8,6 +32,7 @@ fn part1(instructions: &[String]) -> i32 {
     let mut wait_states = 0;
     let mut x_latch: Option<i32> = None;
 
+    let mut crt = String::new();
     let mut crt_col = 0;
 
     for cycle in 0..=240 {
@@ -23,18 +48,11 @@ fn part1(instructions: &[String]) -> i32 {
 
             let instruction = instruction_iter.next();
             match instruction {
-                Some(instruction) => {
-                    let args: Vec<_> = instruction.split_whitespace().collect();
-                    let opcode = args[0];
-                    match opcode {
-                        "addx" => {
-                            x_latch = Some(args[1].parse().unwrap());
-                            wait_states = 1;
-                        }
-                        "noop" => {}
-                        _ => panic!("Invalid opcode"),
-                    }
+                Some(Instruction::Addx(value)) => {
+                    x_latch = Some(*value);
+                    wait_states = 1;
                 }
+                Some(Instruction::Noop) => {}
                 None => break,
             }
         } else {
@@ -42,186 +60,67 @@ fn part1(instructions: &[String]) -> i32 {
         }
 
         if crt_col >= x - 1 && crt_col <= x + 1 {
-            print!("#");
+            crt.push('#');
         }
         else {
-            print!(".");
+            crt.push('.');
         }
 
         crt_col += 1;
         if crt_col == 40 {
             crt_col = 0;
-            println!();
+            crt.push('\n');
         }
     }
 
-    x_at_cycle.values().sum()
+    (x_at_cycle.values().sum(), crt)
 }
 
-fn part2(_instructions: &[String]) -> usize {
-    0
+fn part1(instructions: &[Instruction]) -> i32 {
+    run(instructions).0
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day10.txt");
+fn part2(instructions: &[Instruction]) -> String {
+    let (_, crt) = run(instructions);
+    crt.trim_end().to_string()
+}
 
-    let result_1 = part1(&lines);
-    let result_2 = part2(&lines);
+/// `size` CPU instructions.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let instructions: Vec<_> = (0..size.max(1))
+        .map(|_| match rng.gen_range(0..3) {
+            0 => "noop".to_string(),
+            _ => format!("addx {}", rng.gen_range(-15..=15)),
+        })
+        .collect();
 
-    (result_1.to_string(), result_2.to_string())
+    instructions.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"addx 15
-addx -11
-addx 6
-addx -3
-addx 5
-addx -1
-addx -8
-addx 13
-addx 4
-noop
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx -35
-addx 1
-addx 24
-addx -19
-addx 1
-addx 16
-addx -11
-noop
-noop
-addx 21
-addx -15
-noop
-noop
-addx -3
-addx 9
-addx 1
-addx -3
-addx 8
-addx 1
-addx 5
-noop
-noop
-noop
-noop
-noop
-addx -36
-noop
-addx 1
-addx 7
-noop
-noop
-noop
-addx 2
-addx 6
-noop
-noop
-noop
-noop
-noop
-addx 1
-noop
-noop
-addx 7
-addx 1
-noop
-addx -13
-addx 13
-addx 7
-noop
-addx 1
-addx -33
-noop
-noop
-noop
-addx 2
-noop
-noop
-noop
-addx 8
-noop
-addx -1
-addx 2
-addx 1
-noop
-addx 17
-addx -9
-addx 1
-addx 1
-addx -3
-addx 11
-noop
-noop
-addx 1
-noop
-addx 1
-noop
-noop
-addx -13
-addx -19
-addx 1
-addx 3
-addx 26
-addx -30
-addx 12
-addx -1
-addx 3
-addx 1
-noop
-noop
-noop
-addx -9
-addx 18
-addx 1
-addx 2
-noop
-noop
-addx 9
-noop
-noop
-noop
-addx -1
-addx 2
-addx -37
-addx 1
-addx 3
-noop
-addx 15
-addx -21
-addx 22
-addx -6
-addx 1
-noop
-addx 2
-addx 1
-noop
-addx -10
-noop
-noop
-addx 20
-addx 1
-addx 2
-addx 2
-addx -6
-addx -11
-noop
-noop
-noop",
-    );
-
-    assert_eq!(13140, part1(&lines));
-    // assert_eq!(1, part2(&lines));
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Cathode-Ray Tube",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["simulation", "picture"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_instructions(input)
+    }
+
+    fn part1(&self, instructions: &Self::Input) -> Answer {
+        part1(instructions).into()
+    }
+
+    fn part2(&self, instructions: &Self::Input) -> Answer {
+        Answer::Picture(part2(instructions))
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
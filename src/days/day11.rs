@@ -1,9 +1,15 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
+#[derive(Clone)]
 enum Target {
     Old,
     Value(u64),
 }
 
-struct Monkey {
+#[derive(Clone)]
+pub struct Monkey {
     starting_items: Vec<u64>,
     operation: (&'static str, Target),
     divisible_by: u64,
@@ -13,14 +19,14 @@ struct Monkey {
 }
 
 impl Monkey {
-    pub fn parse(lines: &[String]) -> Result<Self, &'static str> {
+    pub fn parse(block: &str) -> Result<Self, &'static str> {
         let mut starting_items: Option<Vec<u64>> = None;
         let mut operation = ("none", Target::Old);
         let mut divisable_by: Option<u64> = None;
         let mut throw_to_when_true: Option<usize> = None;
         let mut throw_to_when_false: Option<usize> = None;
 
-        for line in lines.iter().skip(1) {
+        for line in block.lines().skip(1) {
             let args: Vec<_> = line.split(':').collect();
             match args[0] {
                 "  Starting items" => {
@@ -72,7 +78,7 @@ impl Monkey {
     }
 }
 
-fn do_round(monkeys: &mut Vec<Monkey>, divide_by_3: bool, modulo: u64) {
+fn do_round(monkeys: &mut [Monkey], divide_by_3: bool, modulo: u64) {
     for i in 0..monkeys.len() {
         let mut items_to_throw: Vec<(usize, _)> = Vec::new();
         {
@@ -109,11 +115,8 @@ fn do_round(monkeys: &mut Vec<Monkey>, divide_by_3: bool, modulo: u64) {
     }
 }
 
-fn part1(lines: &[String]) -> u64 {
-    let mut monkeys: Vec<Monkey> = lines
-        .split(|l| l.is_empty())
-        .map(|chunk| Monkey::parse(chunk).unwrap())
-        .collect();
+fn part1(monkeys: &[Monkey]) -> u64 {
+    let mut monkeys = monkeys.to_vec();
 
     let modulo = monkeys.iter().map(|m| m.divisible_by).product();
 
@@ -133,11 +136,8 @@ fn part1(lines: &[String]) -> u64 {
         .product()
 }
 
-fn part2(lines: &[String]) -> u64 {
-    let mut monkeys: Vec<Monkey> = lines
-        .split(|l| l.is_empty())
-        .map(|chunk| Monkey::parse(chunk).unwrap())
-        .collect();
+fn part2(monkeys: &[Monkey]) -> u64 {
+    let mut monkeys = monkeys.to_vec();
 
     let modulo = monkeys.iter().map(|m| m.divisible_by).product();
 
@@ -157,47 +157,73 @@ fn part2(lines: &[String]) -> u64 {
         .product()
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day11.txt");
+/// Eight monkeys holding `size` items between them, at least one each. Tests divide by
+/// the first eight primes, which keeps `old * old` within a `u64` modulo their product.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut divisors = [2, 3, 5, 7, 11, 13, 17, 19];
+    divisors.shuffle(rng);
+
+    let mut items = vec![1; divisors.len()];
+    for _ in divisors.len()..size {
+        items[rng.gen_range(0..divisors.len())] += 1;
+    }
 
-    let result_1 = part1(&lines);
-    let result_2 = part2(&lines);
+    let monkeys: Vec<_> = divisors
+        .iter()
+        .zip(items)
+        .enumerate()
+        .map(|(i, (divisor, items))| {
+            let items: Vec<_> = (0..items).map(|_| rng.gen_range(50..=99).to_string()).collect();
+            let operation = match rng.gen_range(0..3) {
+                0 => "old * old".to_string(),
+                1 => format!("old * {}", rng.gen_range(2..=19)),
+                _ => format!("old + {}", rng.gen_range(1..=8)),
+            };
+            let mut targets = (0..divisors.len()).filter(|t| *t != i).collect::<Vec<_>>();
+            targets.shuffle(rng);
+
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+                i,
+                items.join(", "),
+                operation,
+                divisor,
+                targets[0],
+                targets[1]
+            )
+        })
+        .collect();
 
-    (result_1.to_string(), result_2.to_string())
+    monkeys.join("\n\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"Monkey 0:
-  Starting items: 79, 98
-  Operation: new = old * 19
-  Test: divisible by 23
-    If true: throw to monkey 2
-    If false: throw to monkey 3
-
-Monkey 1:
-  Starting items: 54, 65, 75, 74
-  Operation: new = old + 6
-  Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
-
-Monkey 2:
-  Starting items: 79, 60, 97
-  Operation: new = old * old
-  Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
-
-Monkey 3:
-  Starting items: 74
-  Operation: new = old + 3
-  Test: divisible by 17
-    If true: throw to monkey 0
-    If false: throw to monkey 1",
-    );
-
-    assert_eq!(10605, part1(&lines));
-    assert_eq!(2713310158, part2(&lines));
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Monkey in the Middle",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["simulation", "math"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        input
+            .blocks()
+            .map(|block| Monkey::parse(block).unwrap())
+            .collect()
+    }
+
+    fn part1(&self, monkeys: &Self::Input) -> Answer {
+        part1(monkeys).into()
+    }
+
+    fn part2(&self, monkeys: &Self::Input) -> Answer {
+        part2(monkeys).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
@@ -1,4 +1,7 @@
 use pathfinding::prelude::bfs;
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 struct Pos(i32, i32);
@@ -57,8 +60,8 @@ impl Pos {
     }
 }
 
-fn part1(lines: &[String]) -> usize {
-    let mut grid = Grid(lines.iter().map(|row| row.chars().collect()).collect());
+fn part1(elevations: &[Vec<char>]) -> usize {
+    let mut grid = Grid(elevations.to_vec());
 
     let (start, goal) = {
         let mut start: Option<Pos> = None;
@@ -85,8 +88,8 @@ fn part1(lines: &[String]) -> usize {
     result.unwrap().len() - 1
 }
 
-fn part2(lines: &[String]) -> usize {
-    let mut grid = Grid(lines.iter().map(|row| row.chars().collect()).collect());
+fn part2(elevations: &[Vec<char>]) -> usize {
+    let mut grid = Grid(elevations.to_vec());
 
     let (starts, goal) = {
         let mut starts = Vec::new();
@@ -116,25 +119,58 @@ fn part2(lines: &[String]) -> usize {
     results.iter().map(|r| r.len()).min().unwrap() - 1
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day12.txt");
-
-    let result_1 = part1(&lines);
-    let result_2 = part2(&lines);
+/// A heightmap `size` wide rising from `S` in the top left to `E` in the bottom right.
+/// Elevation climbs at most one step at a time along the top row and right column, so the
+/// goal is always reachable, and random pits are dug everywhere else.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let (width, height) = (size.max(26), (size / 4).max(2));
+    let span = width + height - 1;
+
+    let rows: Vec<String> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let mut elevation = ((x + y) * 26 / span) as u8;
+                    if y > 0 && x < width - 1 && rng.gen_bool(0.3) {
+                        elevation = rng.gen_range(0..=elevation);
+                    }
+                    match (x, y) {
+                        (0, 0) => 'S',
+                        _ if (x, y) == (width - 1, height - 1) => 'E',
+                        _ => (b'a' + elevation) as char,
+                    }
+                })
+                .collect()
+        })
+        .collect();
 
-    (result_1.to_string(), result_2.to_string())
+    rows.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"Sabqponm
-abcryxxl
-accszExk
-acctuvwj
-abdefghi",
-    );
-
-    assert_eq!(31, part1(&lines));
-    assert_eq!(29, part2(&lines));
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Vec<char>>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Hill Climbing Algorithm",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["grid", "pathfinding"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        input.char_grid()
+    }
+
+    fn part1(&self, elevations: &Self::Input) -> Answer {
+        part1(elevations).into()
+    }
+
+    fn part2(&self, elevations: &Self::Input) -> Answer {
+        part2(elevations).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
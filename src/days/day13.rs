@@ -9,6 +9,9 @@ use nom::{
     sequence::delimited,
     IResult,
 };
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
 
 #[derive(Debug)]
 pub enum Thing {
@@ -82,20 +85,27 @@ fn parse_list(input: &str) -> IResult<&str, Vec<Thing>> {
 
 fn parse_thing(input: &str) -> IResult<&str, Thing> {
     alt((
-        map(parse_value, |v| Thing::Value(v)),
-        map(parse_list, |l| Thing::List(l)),
+        map(parse_value, Thing::Value),
+        map(parse_list, Thing::List),
     ))(input)
 }
 
-fn part1(lines: &[String]) -> usize {
-    let chunks: Vec<_> = lines.split(|line| line.is_empty()).collect();
+fn parse_pairs(input: &Input) -> Vec<(Thing, Thing)> {
+    input
+        .blocks()
+        .map(|block| {
+            let mut lines = block.lines();
+            let (_, packet1) = parse_thing(lines.next().unwrap()).unwrap();
+            let (_, packet2) = parse_thing(lines.next().unwrap()).unwrap();
+            (packet1, packet2)
+        })
+        .collect()
+}
 
+fn part1(pairs: &[(Thing, Thing)]) -> usize {
     let mut count_right_order = Vec::new();
-    for (i, chunk) in chunks.iter().enumerate() {
-        let (_, packet1) = parse_thing(&chunk[0]).unwrap();
-        let (_, packet2) = parse_thing(&chunk[1]).unwrap();
-
-        if packet1.cmp(&packet2) == Ordering::Less {
+    for (i, (packet1, packet2)) in pairs.iter().enumerate() {
+        if packet1.cmp(packet2) == Ordering::Less {
             count_right_order.push(i + 1);
         }
     }
@@ -103,17 +113,14 @@ fn part1(lines: &[String]) -> usize {
     count_right_order.iter().sum()
 }
 
-fn part2(lines: &[String]) -> usize {
-    let chunks: Vec<_> = lines.split(|line| line.is_empty()).collect();
-
-    let mut packets = vec![
+fn part2(pairs: &[(Thing, Thing)]) -> usize {
+    let dividers = [
         Thing::List(vec![Thing::Value(2)]),
         Thing::List(vec![Thing::Value(6)]),
     ];
 
-    for chunk in chunks {
-        let (_, packet1) = parse_thing(&chunk[0]).unwrap();
-        let (_, packet2) = parse_thing(&chunk[1]).unwrap();
+    let mut packets: Vec<&Thing> = dividers.iter().collect();
+    for (packet1, packet2) in pairs {
         packets.push(packet1);
         packets.push(packet2);
     }
@@ -122,26 +129,68 @@ fn part2(lines: &[String]) -> usize {
 
     let index_of_divider_2 = packets
         .iter()
-        .position(|thing| thing == &Thing::List(vec![Thing::Value(2)]))
+        .position(|thing| *thing == &dividers[0])
         .unwrap()
         + 1;
 
     let index_of_divider_6 = packets
         .iter()
-        .position(|thing| thing == &Thing::List(vec![Thing::Value(6)]))
+        .position(|thing| *thing == &dividers[1])
         .unwrap()
         + 1;
 
     index_of_divider_2 * index_of_divider_6
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day13.txt");
+/// `size` pairs of packets nested up to four lists deep.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let pairs: Vec<_> = (0..size.max(1))
+        .map(|_| format!("{}\n{}", generate_packet(rng, 0), generate_packet(rng, 0)))
+        .collect();
 
-    let result_1 = part1(&lines);
-    let result_2 = part2(&lines);
+    pairs.join("\n\n")
+}
+
+fn generate_packet(rng: &mut StdRng, depth: usize) -> String {
+    let items: Vec<_> = (0..rng.gen_range(0..=4))
+        .map(|_| {
+            if depth < 3 && rng.gen_bool(0.3) {
+                generate_packet(rng, depth + 1)
+            } else {
+                rng.gen_range(0..=10).to_string()
+            }
+        })
+        .collect();
 
-    (result_1.to_string(), result_2.to_string())
+    format!("[{}]", items.join(","))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<(Thing, Thing)>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Distress Signal",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["parsing", "recursion"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_pairs(input)
+    }
+
+    fn part1(&self, pairs: &Self::Input) -> Answer {
+        part1(pairs).into()
+    }
+
+    fn part2(&self, pairs: &Self::Input) -> Answer {
+        part2(pairs).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
 
 #[cfg(test)]
@@ -175,35 +224,3 @@ mod parser_tests {
         }
     }
 }
-
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"[1,1,3,1,1]
-[1,1,5,1,1]
-
-[[1],[2,3,4]]
-[[1],4]
-
-[9]
-[[8,7,6]]
-
-[[4,4],4,4]
-[[4,4],4,4,4]
-
-[7,7,7,7]
-[7,7,7]
-
-[]
-[3]
-
-[[[]]]
-[[]]
-
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]",
-    );
-
-    assert_eq!(13, part1(&lines));
-    assert_eq!(140, part2(&lines));
-}
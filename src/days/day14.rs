@@ -1,5 +1,26 @@
 use std::{collections::HashSet, fmt::Display};
 
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
+/// A line of rock, as the points where it changes direction.
+type Path = Vec<(i32, i32)>;
+
+fn parse_paths(input: &Input) -> Vec<Path> {
+    input
+        .lines()
+        .map(|line| {
+            line.split(" -> ")
+                .map(|point| {
+                    let (x, y) = point.split_once(',').expect("Expected x,y");
+                    (x.parse().unwrap(), y.parse().unwrap())
+                })
+                .collect()
+        })
+        .collect()
+}
+
 struct Grid {
     min: (i32, i32),
     max: (i32, i32),
@@ -8,18 +29,15 @@ struct Grid {
 }
 
 impl Grid {
-    pub fn new(lines: &[String]) -> Self {
+    pub fn new(paths: &[Path]) -> Self {
         let mut rocks = HashSet::new();
-        let mut min = (std::i32::MAX, std::i32::MAX);
-        let mut max = (std::i32::MIN, std::i32::MIN);
+        let mut min = (i32::MAX, i32::MAX);
+        let mut max = (i32::MIN, i32::MIN);
 
-        for line in lines {
-            let points: Vec<_> = line.split(" -> ").collect();
+        for path in paths {
             let mut prev: Option<(i32, i32)> = None;
 
-            for point in points {
-                let xy: Vec<_> = point.split(',').collect();
-                let (x, y): (i32, i32) = (xy[0].parse().unwrap(), xy[1].parse().unwrap());
+            for &(x, y) in path {
                 if let Some(p) = prev {
                     let (minx, maxx) = (std::cmp::min(p.0, x), std::cmp::max(p.0, x));
                     let (miny, maxy) = (std::cmp::min(p.1, y), std::cmp::max(p.1, y));
@@ -117,10 +135,10 @@ impl Display for Grid {
     }
 }
 
-fn part1(lines: &[String]) -> usize {
-    let mut grid = Grid::new(lines);
+fn part1(paths: &[Path]) -> usize {
+    let mut grid = Grid::new(paths);
 
-    println!("{}", &grid);
+    crate::verbose!("{}", &grid);
 
     let mut units_of_sand_stuck = 0;
 
@@ -137,10 +155,10 @@ fn part1(lines: &[String]) -> usize {
     units_of_sand_stuck
 }
 
-fn part2(lines: &[String]) -> usize {
-    let mut grid = Grid::new(lines);
+fn part2(paths: &[Path]) -> usize {
+    let mut grid = Grid::new(paths);
 
-    println!("{}", &grid);
+    crate::verbose!("{}", &grid);
 
     let mut units_of_sand_stuck = 0;
 
@@ -158,26 +176,69 @@ fn part2(lines: &[String]) -> usize {
         }
     }
 
-    println!("{}", grid);
+    crate::verbose!("{}", grid);
     units_of_sand_stuck
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day14.txt");
+/// `size` rock paths of straight segments below the sand source. Rock stays strictly
+/// inside the diagonals running down from the source, as sand resting on those could
+/// pile up to the source and part 1 would never end.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    // The area of rock grows with the number of paths, keeping it as dense as at 100 paths
+    let scale = (size as f64 / 100.0).sqrt().max(1.0);
+    let half_width = (50.0 * scale) as i32;
+    let depth = (165.0 * scale) as i32;
+    let max_offset = |y: i32| (y - 1).min(half_width);
+
+    let paths: Vec<_> = (0..size.max(1))
+        .map(|_| {
+            let y = rng.gen_range(5..=5 + depth);
+            let mut point = (rng.gen_range(500 - max_offset(y)..=500 + max_offset(y)), y);
+            let mut points = vec![point];
+            for i in 0..rng.gen_range(1..=4) {
+                let length = rng.gen_range(1..=10) * if rng.gen_bool(0.5) { 1 } else { -1 };
+                // Segments alternate between horizontal and vertical
+                if i % 2 == 0 {
+                    let offset = max_offset(point.1);
+                    point.0 = (point.0 + length).clamp(500 - offset, 500 + offset);
+                } else {
+                    point.1 = (point.1 + length).max(5).max((point.0 - 500).abs() + 1);
+                }
+                points.push(point);
+            }
 
-    let result_1 = part1(&lines);
-    let result_2 = part2(&lines);
+            let points: Vec<_> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+            points.join(" -> ")
+        })
+        .collect();
 
-    (result_1.to_string(), result_2.to_string())
+    paths.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9",
-    );
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Path>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Regolith Reservoir",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["grid", "simulation"],
+    };
 
-    assert_eq!(24, part1(&lines));
-    assert_eq!(93, part2(&lines));
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_paths(input)
+    }
+
+    fn part1(&self, paths: &Self::Input) -> Answer {
+        part1(paths).into()
+    }
+
+    fn part2(&self, paths: &Self::Input) -> Answer {
+        part2(paths).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
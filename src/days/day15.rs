@@ -1,5 +1,8 @@
+use rand::{rngs::StdRng, Rng};
 use scan_fmt::scan_fmt;
 
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
 #[derive(Clone, Copy)]
 struct Range {
     from: i64,
@@ -11,14 +14,14 @@ struct Ranges(Vec<Range>);
 
 impl Ranges {
     pub fn merge(&mut self) {
-        if self.0.len() == 0 {
+        if self.0.is_empty() {
             return;
         }
 
-        self.0.sort_by(|a, b| a.from.cmp(&b.from));
+        self.0.sort_by_key(|r| r.from);
 
         let mut new_ranges = Vec::new();
-        let mut acc = self.0[0].clone();
+        let mut acc = self.0[0];
 
         for cur in self.0.iter().skip(1) {
             if cur.from > acc.to + 1 || cur.to < acc.from - 1 {
@@ -43,41 +46,11 @@ impl Ranges {
     }
 }
 
-#[cfg(test)]
-mod range_tests {
-    use super::{Range, Ranges};
-
-    #[test]
-    fn test_range_merge_1() {
-        let mut ranges = Ranges(vec![Range { from: 0, to: 10 }, Range { from: 5, to: 15 }]);
-        ranges.merge();
-        assert!(ranges.0.len() == 1 && ranges.0[0].from == 0 && ranges.0[0].to == 15);
-    }
-
-    #[test]
-    fn test_range_merge_2() {
-        let mut ranges = Ranges(vec![Range { from: 0, to: 10 }, Range { from: 11, to: 15 }]);
-        ranges.merge();
-        assert!(ranges.0.len() == 1 && ranges.0[0].from == 0 && ranges.0[0].to == 15);
-    }
-
-    #[test]
-    fn test_range_merge_3() {
-        let mut ranges = Ranges(vec![Range { from: 0, to: 10 }, Range { from: 12, to: 15 }]);
-        ranges.merge();
-        assert!(
-            ranges.0.len() == 2
-                && ranges.0[0].from == 0
-                && ranges.0[0].to == 10
-                && ranges.0[1].from == 12
-                && ranges.0[1].to == 15
-        );
-    }
-}
+type Sensor = (i64, i64, i64, i64);
 
-fn get_results(lines: &[String], part1_line_to_test: i64, search_area: i64) -> (i64, i64) {
-    let pos_lists: Vec<_> = lines
-        .iter()
+fn parse_sensors(input: &Input) -> Vec<Sensor> {
+    input
+        .lines()
         .map(|line| {
             scan_fmt!(
                 line,
@@ -89,54 +62,107 @@ fn get_results(lines: &[String], part1_line_to_test: i64, search_area: i64) -> (
             )
             .unwrap()
         })
-        .collect();
-
-    let mut part1_result = 0;
-    let mut part2_result = 0;
+        .collect()
+}
 
-    for y in 0..=search_area {
-        let mut ranges = Ranges::default();
+fn ranges_at_row(sensors: &[Sensor], y: i64) -> Ranges {
+    let mut ranges = Ranges::default();
 
-        for (sensor_x, sensor_y, beacon_x, beacon_y) in &pos_lists {
-            // Go over each row, pushing ranges to the set of ranges
-            let disty = (beacon_y - sensor_y).abs();
-            let distx = (beacon_x - sensor_x).abs();
-            let distance = distx + disty;
+    for (sensor_x, sensor_y, beacon_x, beacon_y) in sensors {
+        // Go over each row, pushing ranges to the set of ranges
+        let disty = (beacon_y - sensor_y).abs();
+        let distx = (beacon_x - sensor_x).abs();
+        let distance = distx + disty;
 
-            if y >= sensor_y - distance && y <= sensor_y + distance {
-                let x_dist = distance - (y - sensor_y).abs();
+        if y >= sensor_y - distance && y <= sensor_y + distance {
+            let x_dist = distance - (y - sensor_y).abs();
 
-                ranges.push(Range {
-                    from: sensor_x - x_dist,
-                    to: sensor_x + x_dist,
-                });
-            }
+            ranges.push(Range {
+                from: sensor_x - x_dist,
+                to: sensor_x + x_dist,
+            });
         }
+    }
 
-        ranges.merge();
-        if y == part1_line_to_test {
-            part1_result = ranges.0.iter().map(|r| r.to - r.from).sum();
-        }
+    ranges.merge();
+    ranges
+}
+
+fn part1(sensors: &[Sensor], line_to_test: i64) -> i64 {
+    ranges_at_row(sensors, line_to_test)
+        .0
+        .iter()
+        .map(|r| r.to - r.from)
+        .sum()
+}
+
+fn part2(sensors: &[Sensor], search_area: i64) -> i64 {
+    for y in 0..=search_area {
+        let ranges = ranges_at_row(sensors, y);
         if ranges.0.len() > 1 {
             let x = ranges.0[1].from - 1;
-            part2_result = x * 4000000 + y;
+            return x * 4000000 + y;
         }
     }
 
-    (part1_result, part2_result)
+    0
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day15.txt");
-    let (result_1, result_2) = get_results(&lines, 2000000, 4000000);
+/// `size` sensors spread over the real puzzle's search area, each with its beacon nearby.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let sensors: Vec<_> = (0..size.max(1))
+        .map(|_| {
+            let (x, y) = (rng.gen_range(0..=4000000), rng.gen_range(0..=4000000));
+            let beacon_x = x + rng.gen_range(-200000..=200000);
+            let beacon_y = y + rng.gen_range(-200000..=200000);
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                x, y, beacon_x, beacon_y
+            )
+        })
+        .collect();
 
-    (result_1.to_string(), result_2.to_string())
+    sensors.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<Sensor>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Beacon Exclusion Zone",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["ranges", "geometry"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_sensors(input)
+    }
+
+    fn part1(&self, sensors: &Self::Input) -> Answer {
+        part1(sensors, 2000000).into()
+    }
+
+    fn part2(&self, sensors: &Self::Input) -> Answer {
+        part2(sensors, 4000000).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod example_tests {
+    use super::{parse_sensors, part1, part2};
+    use crate::aoc::Input;
+
+    // The example asks about a smaller row and search area than the real puzzle
+    #[test]
+    fn test_example() {
+        let input = Input::new(
+            r"Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
 Sensor at x=13, y=2: closest beacon is at x=15, y=3
 Sensor at x=12, y=14: closest beacon is at x=10, y=16
@@ -150,10 +176,103 @@ Sensor at x=17, y=20: closest beacon is at x=21, y=22
 Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3",
-    );
+        );
 
-    let (result_1, result_2) = get_results(&lines, 10, 20);
+        let sensors = parse_sensors(&input);
+        assert_eq!(26, part1(&sensors, 10));
+        assert_eq!(56000011, part2(&sensors, 20));
+    }
+}
 
-    assert_eq!(26, result_1);
-    assert_eq!(56000011, result_2);
+#[cfg(test)]
+mod range_tests {
+    use super::{Range, Ranges};
+
+    #[test]
+    fn test_range_merge_1() {
+        let mut ranges = Ranges(vec![Range { from: 0, to: 10 }, Range { from: 5, to: 15 }]);
+        ranges.merge();
+        assert!(ranges.0.len() == 1 && ranges.0[0].from == 0 && ranges.0[0].to == 15);
+    }
+
+    #[test]
+    fn test_range_merge_2() {
+        let mut ranges = Ranges(vec![Range { from: 0, to: 10 }, Range { from: 11, to: 15 }]);
+        ranges.merge();
+        assert!(ranges.0.len() == 1 && ranges.0[0].from == 0 && ranges.0[0].to == 15);
+    }
+
+    #[test]
+    fn test_range_merge_3() {
+        let mut ranges = Ranges(vec![Range { from: 0, to: 10 }, Range { from: 12, to: 15 }]);
+        ranges.merge();
+        assert!(
+            ranges.0.len() == 2
+                && ranges.0[0].from == 0
+                && ranges.0[0].to == 10
+                && ranges.0[1].from == 12
+                && ranges.0[1].to == 15
+        );
+    }
+}
+
+#[cfg(test)]
+mod differential_tests {
+    use std::collections::BTreeSet;
+
+    use rand::Rng;
+
+    use super::{Range, Ranges};
+    use crate::differential;
+
+    /// Marks every covered position and reads back the runs of consecutive positions.
+    fn merge_naive(ranges: &[(i64, i64)]) -> Vec<(i64, i64)> {
+        let covered: BTreeSet<i64> = ranges.iter().flat_map(|&(from, to)| from..=to).collect();
+
+        let mut merged: Vec<(i64, i64)> = Vec::new();
+        for x in covered {
+            match merged.last_mut() {
+                Some(last) if last.1 + 1 == x => last.1 = x,
+                _ => merged.push((x, x)),
+            }
+        }
+
+        merged
+    }
+
+    #[test]
+    fn test_merge_matches_naive() {
+        differential::check(
+            |rng| {
+                (0..rng.gen_range(0..=8))
+                    .map(|_| {
+                        let from = rng.gen_range(-20..=20);
+                        (from, from + rng.gen_range(0..=10))
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |ranges| {
+                let mut candidates = differential::shrink_vec(ranges);
+                for (i, &(from, to)) in ranges.iter().enumerate() {
+                    if from < to {
+                        let mut narrower = ranges.clone();
+                        narrower[i].1 -= 1;
+                        candidates.push(narrower);
+                    }
+                }
+                candidates
+            },
+            |ranges| {
+                let mut merged = Ranges(
+                    ranges
+                        .iter()
+                        .map(|&(from, to)| Range { from, to })
+                        .collect(),
+                );
+                merged.merge();
+                merged.0.iter().map(|r| (r.from, r.to)).collect::<Vec<_>>()
+            },
+            |ranges| merge_naive(ranges),
+        );
+    }
 }
@@ -1,10 +1,16 @@
 use std::{collections::HashMap, str::FromStr};
 
-use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
+use rand::{
+    rngs::{StdRng, ThreadRng},
+    seq::SliceRandom,
+    Rng,
+};
 use regex::Regex;
 
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
 #[derive(Debug)]
-struct Valve {
+pub struct Valve {
     id: String,
     flow_rate: u64,
     leads_to: Vec<String>,
@@ -45,11 +51,6 @@ struct SearchState {
 }
 
 impl SearchState {
-    pub fn score(&self) -> u64 {
-        // Öhhhhh
-        self.current_pressure_released + self.open_valves.len() as u64
-    }
-
     pub fn mutate(
         &self,
         valves: &HashMap<String, Valve>,
@@ -80,20 +81,22 @@ impl SearchState {
     }
 }
 
-fn part_1(lines: &[String]) -> u64 {
-    let valves: HashMap<_, _> = lines
-        .iter()
+fn parse_valves(input: &Input) -> HashMap<String, Valve> {
+    input
+        .lines()
         .map(|line| {
             let v = line.parse::<Valve>().unwrap();
             (v.id.clone(), v)
         })
-        .collect();
+        .collect()
+}
 
+fn part_1(valves: &HashMap<String, Valve>) -> u64 {
     let mut max_pressure_released = 0;
 
     for _ in 0..10000 {
         let mut state = SearchState {
-            id: "DD".to_string(),
+            id: "AA".to_string(),
             minutes: 1,
             open_valves: Vec::new(),
             current_pressure_released: 0,
@@ -106,7 +109,7 @@ fn part_1(lines: &[String]) -> u64 {
             // println!("{:?}", state);
             
             // 2. Clone state and mutate
-            let new_state = state.mutate(&valves, &mut rng);
+            let new_state = state.mutate(valves, &mut rng);
 
             // 3. Check if it's better than before
             if new_state.minutes <= 30 {
@@ -119,37 +122,93 @@ fn part_1(lines: &[String]) -> u64 {
         }
     }
 
-    println!("Max pressure: {}", max_pressure_released);
+    crate::debug!("Max pressure: {}", max_pressure_released);
     
     max_pressure_released
     
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day15.txt");
-    let result_1 = part_1(&lines);
-    let result_2 = 0;
+/// A connected graph of `size` valves including `AA`, about half of them without flow.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut names: Vec<String> = Vec::new();
+    names.push("AA".to_string());
+    while names.len() < size.clamp(2, 26 * 26) {
+        let name: String = (0..2).map(|_| rng.gen_range('A'..='Z')).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    // A random spanning tree keeps every valve reachable, plus a few shortcuts
+    let mut tunnels = vec![Vec::new(); names.len()];
+    for i in 1..names.len() {
+        let j = rng.gen_range(0..i);
+        tunnels[i].push(j);
+        tunnels[j].push(i);
+    }
+    for _ in 0..names.len() / 2 {
+        let (i, j) = (rng.gen_range(0..names.len()), rng.gen_range(0..names.len()));
+        if i != j && !tunnels[i].contains(&j) {
+            tunnels[i].push(j);
+            tunnels[j].push(i);
+        }
+    }
 
-    (result_1.to_string(), result_2.to_string())
+    let valves: Vec<_> = names
+        .iter()
+        .zip(tunnels.iter_mut())
+        .enumerate()
+        .map(|(i, (name, tunnels))| {
+            let rate = if i == 0 || rng.gen_bool(0.5) {
+                0
+            } else {
+                rng.gen_range(1..=25)
+            };
+            tunnels.shuffle(rng);
+            let leads_to: Vec<_> = tunnels.iter().map(|t| names[*t].as_str()).collect();
+            if leads_to.len() == 1 {
+                format!(
+                    "Valve {} has flow rate={}; tunnel leads to valve {}",
+                    name, rate, leads_to[0]
+                )
+            } else {
+                format!(
+                    "Valve {} has flow rate={}; tunnels lead to valves {}",
+                    name,
+                    rate,
+                    leads_to.join(", ")
+                )
+            }
+        })
+        .collect();
+
+    valves.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-Valve BB has flow rate=13; tunnels lead to valves CC, AA
-Valve CC has flow rate=2; tunnels lead to valves DD, BB
-Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-Valve EE has flow rate=3; tunnels lead to valves FF, DD
-Valve FF has flow rate=0; tunnels lead to valves EE, GG
-Valve GG has flow rate=0; tunnels lead to valves FF, HH
-Valve HH has flow rate=22; tunnel leads to valve GG
-Valve II has flow rate=0; tunnels lead to valves AA, JJ
-Valve JJ has flow rate=21; tunnel leads to valve II",
-    );
-
-    let result_1 = part_1(&lines);
-
-    // assert_eq!(1651, result_1);
-    // assert_eq!(56000011, result_2);
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = HashMap<String, Valve>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Proboscidea Volcanium",
+        parts: [PartStatus::Wrong, PartStatus::Stub],
+        tags: &["graph", "search"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_valves(input)
+    }
+
+    fn part1(&self, valves: &Self::Input) -> Answer {
+        part_1(valves).into()
+    }
+
+    fn part2(&self, _valves: &Self::Input) -> Answer {
+        Answer::NotImplemented
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
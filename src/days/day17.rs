@@ -1,4 +1,6 @@
-use std::hash::Hash;
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
 
 type Piece = Vec<Vec<u8>>;
 
@@ -9,7 +11,7 @@ struct Playarea {
 impl Playarea {
     fn make_room(&mut self, bot_y: usize, piece_height: usize) {
         let top_y = bot_y + piece_height;
-        while self.rows.len() < (top_y as usize) {
+        while self.rows.len() < top_y {
             self.rows.push([0, 0, 0, 0, 0, 0, 0]);
         }
     }
@@ -18,9 +20,9 @@ impl Playarea {
         let piece_height = piece.len();
         self.make_room(bot_y, piece_height);
 
-        for py in 0..piece_height {
-            for px in 0..piece[py].len() {
-                if piece[py][px] == 1 {
+        for (py, piece_row) in piece.iter().enumerate() {
+            for (px, cell) in piece_row.iter().enumerate() {
+                if *cell == 1 {
                     self.rows[bot_y + py][x + px] = 1;
                 }
             }
@@ -28,10 +30,10 @@ impl Playarea {
     }
 
     pub fn collides(&mut self, bot_y: usize, x: usize, piece: &Piece) -> bool {
-        for py in 0..piece.len() {
+        for (py, piece_row) in piece.iter().enumerate() {
             let ty = bot_y + py;
 
-            for (px, cell) in piece[py].iter().enumerate() {
+            for (px, cell) in piece_row.iter().enumerate() {
                 let tx = x + px;
                 if tx > 6 {
                     return true;
@@ -49,7 +51,26 @@ impl Playarea {
     }
 }
 
-fn simulate(jetstream_line: &String, do_until_repetition: bool) -> usize {
+#[derive(Clone, Copy)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+fn parse_jets(input: &Input) -> Vec<Jet> {
+    input
+        .as_str()
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '<' => Jet::Left,
+            '>' => Jet::Right,
+            _ => panic!("Invalid jet: {}", c),
+        })
+        .collect()
+}
+
+fn simulate(jetstream: &[Jet], do_until_repetition: bool) -> usize {
     let rock_shapes: [Piece; 5] = [
         vec![vec![1, 1, 1, 1]],
         vec![vec![0, 1, 0], vec![1, 1, 1], vec![0, 1, 0]],
@@ -62,7 +83,6 @@ fn simulate(jetstream_line: &String, do_until_repetition: bool) -> usize {
         rows: vec![[1, 1, 1, 1, 1, 1, 1]],
     };
 
-    let jetstream: Vec<_> = jetstream_line.bytes().collect();
     let mut jetstream_cycle = jetstream.iter().cycle();
     let mut rock_stream = rock_shapes.iter().cycle();
 
@@ -78,17 +98,16 @@ fn simulate(jetstream_line: &String, do_until_repetition: bool) -> usize {
         finished_rocks < 2022
     } {
         match jetstream_cycle.next().unwrap() {
-            b'>' => {
+            Jet::Right => {
                 if !rows.collides(rock_y, rock_x + 1, current_rock) {
                     rock_x += 1;
                 }
             }
-            b'<' => {
+            Jet::Left => {
                 if rock_x > 0 && !rows.collides(rock_y, rock_x - 1, current_rock) {
                     rock_x -= 1;
                 }
             }
-            _ => panic!(),
         }
 
         if rows.collides(rock_y - 1, rock_x, current_rock) {
@@ -105,29 +124,45 @@ fn simulate(jetstream_line: &String, do_until_repetition: bool) -> usize {
     rows.rows.len() - 1
 }
 
-fn part_1(line: &String) -> usize {
-    simulate(line, false)
+fn part_1(jets: &[Jet]) -> usize {
+    simulate(jets, false)
 }
 
-fn part_2(line: &String) -> usize {
-    simulate(line, true)
+fn part_2(jets: &[Jet]) -> usize {
+    simulate(jets, true)
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day17.txt");
-    let result_1 = part_1(&lines[0]);
-    let result_2 = part_2(&lines[0]);
-
-    (result_1.to_string(), result_2.to_string())
+/// A jet pattern of `size` pushes.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| if rng.gen_bool(0.5) { '<' } else { '>' })
+        .collect()
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> =
-        crate::aoc::lines_from_test(r">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+pub struct Day17;
 
-    let result_1 = part_1(&lines[0]);
-    let result_2 = part_2(&lines[0]);
+impl Solution for Day17 {
+    type Input = Vec<Jet>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Pyroclastic Flow",
+        parts: [PartStatus::Solved, PartStatus::Wrong],
+        tags: &["simulation", "cycles"],
+    };
 
-    assert_eq!(3068, result_1);
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_jets(input)
+    }
+
+    fn part1(&self, jets: &Self::Input) -> Answer {
+        part_1(jets).into()
+    }
+
+    fn part2(&self, jets: &Self::Input) -> Answer {
+        part_2(jets).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
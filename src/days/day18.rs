@@ -1,14 +1,16 @@
 use std::collections::HashSet;
 
-use scan_fmt::scan_fmt;
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
 
 type Point = (i32, i32, i32);
 
-fn part_1(coords: &Vec<Point>) -> usize {
+fn part_1(coords: &[Point]) -> usize {
     let mut grid = HashSet::new();
 
     for coord in coords.iter() {
-        grid.insert(coord.clone());
+        grid.insert(*coord);
     }
 
     // Now count
@@ -44,7 +46,7 @@ fn part_1(coords: &Vec<Point>) -> usize {
     num_surfaces_visible
 }
 
-fn part_2(coords: &Vec<Point>) -> usize {
+fn part_2(coords: &[Point]) -> usize {
     let mut rocks = HashSet::new();
 
     let mut min = (100, 100, 100);
@@ -70,7 +72,7 @@ fn part_2(coords: &Vec<Point>) -> usize {
         if coord.2 > max.2 {
             max.2 = coord.2;
         }
-        rocks.insert(coord.clone());
+        rocks.insert(*coord);
     }
 
     // Inflate bounding box by one unit
@@ -125,60 +127,147 @@ fn part_2(coords: &Vec<Point>) -> usize {
     num_faces
 }
 
-fn parse_lines(lines: &[String]) -> Vec<Point> {
-    lines
-        .iter()
-        .map(|line| scan_fmt!(line, "{d},{d},{d}", i32, i32, i32).unwrap())
+fn parse_lines(input: &Input) -> Vec<Point> {
+    input
+        .ints()
+        .collect::<Vec<i32>>()
+        .chunks_exact(3)
+        .map(|xyz| (xyz[0], xyz[1], xyz[2]))
         .collect()
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day18.txt");
-    let coords = parse_lines(&lines);
-    let result_1 = part_1(&coords);
-    let result_2 = part_2(&coords);
+/// `size` distinct cubes packed into a box about twice their volume.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let side = ((size.max(1) * 2) as f64).cbrt().ceil().max(3.0) as i32;
+
+    let mut cubes = HashSet::new();
+    let mut lines = Vec::new();
+    while lines.len() < size.max(1) {
+        let cube = (
+            rng.gen_range(1..=side),
+            rng.gen_range(1..=side),
+            rng.gen_range(1..=side),
+        );
+        if cubes.insert(cube) {
+            lines.push(format!("{},{},{}", cube.0, cube.1, cube.2));
+        }
+    }
 
-    (result_1.to_string(), result_2.to_string())
+    lines.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"1,1,1
-2,1,1
-",
-    );
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Point>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Boiling Boulders",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["3d", "flood fill"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_lines(input)
+    }
+
+    fn part1(&self, coords: &Self::Input) -> Answer {
+        part_1(coords).into()
+    }
 
-    let coords = parse_lines(&lines);
-    let result_1 = part_1(&coords);
-    let result_2 = part_2(&coords);
+    fn part2(&self, coords: &Self::Input) -> Answer {
+        part_2(coords).into()
+    }
 
-    assert_eq!(10, result_1);
-    assert_eq!(10, result_2);
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
 
-#[test]
-fn it_works_2() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"2,2,2
-1,2,2
-3,2,2
-2,1,2
-2,3,2
-2,2,1
-2,2,3
-2,2,4
-2,2,6
-1,2,5
-3,2,5
-2,1,5
-2,3,5",
-    );
-
-    let coords = parse_lines(&lines);
-    let result_1 = part_1(&coords);
-    let result_2 = part_2(&coords);
-
-    assert_eq!(64, result_1);
-    assert_eq!(58, result_2);
+#[cfg(test)]
+mod differential_tests {
+    use std::collections::HashSet;
+
+    use rand::Rng;
+
+    use super::{part_1, part_2, Point};
+    use crate::differential;
+
+    const SIDES: [Point; 6] = [
+        (-1, 0, 0),
+        (1, 0, 0),
+        (0, -1, 0),
+        (0, 1, 0),
+        (0, 0, -1),
+        (0, 0, 1),
+    ];
+
+    /// Counts every face next to air and, separately, the faces whose air can walk out of the
+    /// droplet's bounding box, searching again from each face.
+    fn naive(cubes: &[Point]) -> (usize, usize) {
+        let rocks: HashSet<Point> = cubes.iter().copied().collect();
+        let outside = |p: Point| {
+            cubes.iter().all(|c| p.0 < c.0)
+                || cubes.iter().all(|c| p.0 > c.0)
+                || cubes.iter().all(|c| p.1 < c.1)
+                || cubes.iter().all(|c| p.1 > c.1)
+                || cubes.iter().all(|c| p.2 < c.2)
+                || cubes.iter().all(|c| p.2 > c.2)
+        };
+        let escapes = |start: Point| {
+            let mut seen = HashSet::from([start]);
+            let mut queue = vec![start];
+            while let Some(p) = queue.pop() {
+                if outside(p) {
+                    return true;
+                }
+                for (dx, dy, dz) in SIDES {
+                    let next = (p.0 + dx, p.1 + dy, p.2 + dz);
+                    if !rocks.contains(&next) && seen.insert(next) {
+                        queue.push(next);
+                    }
+                }
+            }
+            false
+        };
+
+        let mut surface = 0;
+        let mut exterior = 0;
+        for &(x, y, z) in cubes {
+            for (dx, dy, dz) in SIDES {
+                let air = (x + dx, y + dy, z + dz);
+                if !rocks.contains(&air) {
+                    surface += 1;
+                    if escapes(air) {
+                        exterior += 1;
+                    }
+                }
+            }
+        }
+
+        (surface, exterior)
+    }
+
+    #[test]
+    fn test_surface_area_matches_naive() {
+        differential::check(
+            |rng| {
+                let mut cubes: Vec<Point> = Vec::new();
+                for _ in 0..rng.gen_range(1..=40) {
+                    let cube = (
+                        rng.gen_range(0..=4),
+                        rng.gen_range(0..=4),
+                        rng.gen_range(0..=4),
+                    );
+                    if !cubes.contains(&cube) {
+                        cubes.push(cube);
+                    }
+                }
+                cubes
+            },
+            |cubes| differential::shrink_vec(cubes),
+            |cubes| (part_1(cubes), part_2(cubes)),
+            |cubes| naive(cubes),
+        );
+    }
 }
@@ -1,73 +1,100 @@
-fn accumulate_scores(lines: &[String], f: fn(&str, &str) -> u32) -> u32 {
-    let mut total_score: u32 = 0;
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
+type Round = (u8, u8);
+
+fn parse_rounds(input: &Input) -> Vec<Round> {
+    input
+        .lines()
+        .map(|line| match line.as_bytes() {
+            [opponent, b' ', yours] => (*opponent, *yours),
+            _ => panic!("Invalid round: {}", line),
+        })
+        .collect()
+}
 
-    for line in lines {
-        let mut splitted = line.split(' ');
-        let opponent = splitted.next().unwrap();
-        let yours = splitted.next().unwrap();
+fn accumulate_scores(rounds: &[Round], f: fn(u8, u8) -> u32) -> u32 {
+    let mut total_score: u32 = 0;
 
-        total_score += f(opponent, yours);
+    for (opponent, yours) in rounds {
+        total_score += f(*opponent, *yours);
     }
 
     total_score
 }
 
 #[allow(clippy::identity_op)]
-fn part1(lines: &[String]) -> u32 {
-    accumulate_scores(lines, |opponent, yours| match (opponent, yours) {
-        ("A", "X") => 1 + 3,
-        ("A", "Y") => 2 + 6,
-        ("A", "Z") => 3 + 0,
+fn part1(rounds: &[Round]) -> u32 {
+    accumulate_scores(rounds, |opponent, yours| match (opponent, yours) {
+        (b'A', b'X') => 1 + 3,
+        (b'A', b'Y') => 2 + 6,
+        (b'A', b'Z') => 3 + 0,
 
-        ("B", "X") => 1 + 0,
-        ("B", "Y") => 2 + 3,
-        ("B", "Z") => 3 + 6,
+        (b'B', b'X') => 1 + 0,
+        (b'B', b'Y') => 2 + 3,
+        (b'B', b'Z') => 3 + 6,
 
-        ("C", "X") => 1 + 6,
-        ("C", "Y") => 2 + 0,
-        ("C", "Z") => 3 + 3,
+        (b'C', b'X') => 1 + 6,
+        (b'C', b'Y') => 2 + 0,
+        (b'C', b'Z') => 3 + 3,
 
         _ => panic!("Invalid move"),
     })
 }
 
 #[allow(clippy::identity_op)]
-fn part2(lines: &[String]) -> u32 {
-    accumulate_scores(lines, |opponent, yours| match (opponent, yours) {
-        ("A", "X") => 3 + 0,
-        ("A", "Y") => 1 + 3,
-        ("A", "Z") => 2 + 6,
+fn part2(rounds: &[Round]) -> u32 {
+    accumulate_scores(rounds, |opponent, yours| match (opponent, yours) {
+        (b'A', b'X') => 3 + 0,
+        (b'A', b'Y') => 1 + 3,
+        (b'A', b'Z') => 2 + 6,
 
-        ("B", "X") => 1 + 0,
-        ("B", "Y") => 2 + 3,
-        ("B", "Z") => 3 + 6,
+        (b'B', b'X') => 1 + 0,
+        (b'B', b'Y') => 2 + 3,
+        (b'B', b'Z') => 3 + 6,
 
-        ("C", "X") => 2 + 0,
-        ("C", "Y") => 3 + 3,
-        ("C", "Z") => 1 + 6,
+        (b'C', b'X') => 2 + 0,
+        (b'C', b'Y') => 3 + 3,
+        (b'C', b'Z') => 1 + 6,
 
         _ => panic!("Invalid move"),
     })
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day2.txt");
-    let day1_result = part1(&lines);
-    let day2_result = part2(&lines);
+/// A strategy guide of `size` rounds.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let rounds: Vec<_> = (0..size.max(1))
+        .map(|_| format!("{} {}", rng.gen_range('A'..='C'), rng.gen_range('X'..='Z')))
+        .collect();
 
-    (day1_result.to_string(), day2_result.to_string())
+    rounds.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"A Y
-B X
-C Z",
-    );
-
-    let part1_result = part1(&lines);
-    let part2_result = part2(&lines);
-    assert_eq!(15, part1_result);
-    assert_eq!(12, part2_result);
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Round>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Rock Paper Scissors",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["game"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_rounds(input)
+    }
+
+    fn part1(&self, rounds: &Self::Input) -> Answer {
+        part1(rounds).into()
+    }
+
+    fn part2(&self, rounds: &Self::Input) -> Answer {
+        part2(rounds).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
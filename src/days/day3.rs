@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
 fn get_priority(c: char) -> u32 {
     let ascii_value = c as u32;
     if ascii_value >= ('a' as u32) {
@@ -9,30 +13,40 @@ fn get_priority(c: char) -> u32 {
     }
 }
 
-fn part1(lines: &[String]) -> u32 {
+/// A rucksack's items, by priority.
+type Rucksack = Vec<u32>;
+
+fn parse_rucksacks(input: &Input) -> Vec<Rucksack> {
+    input
+        .lines()
+        .map(|line| line.chars().map(get_priority).collect())
+        .collect()
+}
+
+fn part1(rucksacks: &[Rucksack]) -> u32 {
     let mut priorities: Vec<u32> = Vec::new();
 
-    for line in lines {
-        let half = line.len() / 2;
-        let (compartment1, compartment2) = (&line[0..half], &line[half..]);
-        let item_in_both = compartment1.chars().find(|c| compartment2.chars().any(|c2| c2 == *c)).unwrap();
+    for rucksack in rucksacks {
+        let half = rucksack.len() / 2;
+        let (compartment1, compartment2) = (&rucksack[0..half], &rucksack[half..]);
+        let item_in_both = compartment1.iter().find(|c| compartment2.contains(c)).unwrap();
 
-        priorities.push(get_priority(item_in_both));
+        priorities.push(*item_in_both);
     }
 
     priorities.iter().sum()
 }
 
-fn part2(lines: &[String]) -> u32 {
+fn part2(rucksacks: &[Rucksack]) -> u32 {
     let mut priorities: Vec<u32> = Vec::new();
 
-    for group_of_three in lines.chunks(3) {
+    for group_of_three in rucksacks.chunks(3) {
         // Keep track of which items each elf has
-        let mut total_items_by_elf: HashMap<char, [bool; 3]> = HashMap::new();
+        let mut total_items_by_elf: HashMap<u32, [bool; 3]> = HashMap::new();
 
         for (elf_index, elf_backpack) in group_of_three.iter().enumerate() {
-            for item in elf_backpack.chars() {
-                let item_record = total_items_by_elf.entry(item).or_insert([false; 3]);
+            for item in elf_backpack {
+                let item_record = total_items_by_elf.entry(*item).or_insert([false; 3]);
                 // we have it
                 item_record[elf_index] = true;
             }
@@ -41,33 +55,67 @@ fn part2(lines: &[String]) -> u32 {
         // Find the item that all three elves has
         let item_in_all_backpacks = total_items_by_elf.iter().find(|(_, a)| a.iter().all(|i| *i)).unwrap().0;
 
-        priorities.push(get_priority(*item_in_all_backpacks));
+        priorities.push(*item_in_all_backpacks);
     }
 
     priorities.iter().sum()
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day3.txt");
-    let day1_result = part1(&lines);
-    let day2_result = part2(&lines);
+/// `size` rucksacks, rounded up to whole groups of three. The compartments of a rucksack
+/// share exactly one item and a group shares only its badge.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let all_items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut rucksacks = Vec::new();
+
+    for _ in 0..size.div_ceil(3).max(1) {
+        let mut items = all_items.clone();
+        items.shuffle(rng);
+        let badge = items[0];
+
+        // Each elf packs from its own 17 items, so the badge is all they have in common
+        for pool in items[1..].chunks(17) {
+            let (shared, rest) = pool.split_first().unwrap();
+            let (left_pool, right_pool) = rest.split_at(8);
+            let extra = rng.gen_range(0..=6);
+
+            let mut left = vec![*shared, badge];
+            left.extend(left_pool.choose_multiple(rng, extra));
+            let mut right = vec![*shared];
+            right.extend(right_pool.choose_multiple(rng, extra + 1));
+            left.shuffle(rng);
+            right.shuffle(rng);
+
+            rucksacks.push(left.into_iter().chain(right).collect::<String>());
+        }
+    }
 
-    (day1_result.to_string(), day2_result.to_string())
+    rucksacks.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw",
-    );
-
-    let part1_result = part1(&lines);
-    let part2_result = part2(&lines);
-    assert_eq!(157, part1_result);
-    assert_eq!(70, part2_result);
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Rucksack Reorganization",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["strings", "sets"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_rucksacks(input)
+    }
+
+    fn part1(&self, rucksacks: &Self::Input) -> Answer {
+        part1(rucksacks).into()
+    }
+
+    fn part2(&self, rucksacks: &Self::Input) -> Answer {
+        part2(rucksacks).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
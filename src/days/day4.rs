@@ -1,4 +1,6 @@
-use std::str::FromStr;
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{self, Answer, Input, Metadata, PartStatus, Solution};
 
 struct Range {
     from: u32,
@@ -15,24 +17,25 @@ impl Range {
     }
 }
 
-impl FromStr for Range {
-    type Err = &'static str;
+/// The section ranges of a pair of elves, as `[from1, to1, from2, to2]`.
+type Pair = [u32; 4];
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut fromto = s.split('-');
-        let from: u32 = fromto.next().expect("Unexpected EOF").parse().expect("Failed to parse to u32");
-        let to: u32 = fromto.next().expect("Unexpected EOF").parse().expect("Failed to parse to u32");
-        Ok(Self { from, to })
-    }
+fn parse_pairs(input: &Input) -> Vec<Pair> {
+    input
+        .lines()
+        .map(|line| {
+            let sections: Vec<u32> = aoc::ints(line).collect();
+            sections.try_into().expect("Expected two ranges")
+        })
+        .collect()
 }
 
-fn count_ranges(lines: &[String], accumulator: fn(Range, Range) -> bool) -> u32 {
+fn count_ranges(pairs: &[Pair], accumulator: fn(Range, Range) -> bool) -> u32 {
     let mut counted = 0;
 
-    for ranges in lines {
-        let mut splitted = ranges.split(',');
-        let range1: Range = splitted.next().unwrap().parse().unwrap();
-        let range2: Range = splitted.next().unwrap().parse().unwrap();
+    for &[from1, to1, from2, to2] in pairs {
+        let range1 = Range { from: from1, to: to1 };
+        let range2 = Range { from: from2, to: to2 };
 
         if accumulator(range1, range2) {
             counted += 1;
@@ -41,35 +44,51 @@ fn count_ranges(lines: &[String], accumulator: fn(Range, Range) -> bool) -> u32
     counted
 }
 
-fn part1(lines: &[String]) -> u32 {
-    count_ranges(lines, |range1, range2| range1.fully_contains(&range2) || range2.fully_contains(&range1))
+fn part1(pairs: &[Pair]) -> u32 {
+    count_ranges(pairs, |range1, range2| range1.fully_contains(&range2) || range2.fully_contains(&range1))
 }
 
-fn part2(lines: &[String]) -> u32 {
-    count_ranges(lines, |range1, range2| range1.overlaps(&range2))
+fn part2(pairs: &[Pair]) -> u32 {
+    count_ranges(pairs, |range1, range2| range1.overlaps(&range2))
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day4.txt");
-    let day1_result = part1(&lines);
-    let day2_result = part2(&lines);
+/// `size` pairs of section assignments.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut range = || {
+        let from = rng.gen_range(1..=99);
+        format!("{}-{}", from, rng.gen_range(from..=99))
+    };
+    let pairs: Vec<_> = (0..size.max(1))
+        .map(|_| format!("{},{}", range(), range()))
+        .collect();
 
-    (day1_result.to_string(), day2_result.to_string())
+    pairs.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8",
-    );
-
-    let part1_result = part1(&lines);
-    let part2_result = part2(&lines);
-    assert_eq!(2, part1_result);
-    assert_eq!(4, part2_result);
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Pair>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Camp Cleanup",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["ranges"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_pairs(input)
+    }
+
+    fn part1(&self, pairs: &Self::Input) -> Answer {
+        part1(pairs).into()
+    }
+
+    fn part2(&self, pairs: &Self::Input) -> Answer {
+        part2(pairs).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
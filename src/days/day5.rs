@@ -1,37 +1,55 @@
+use rand::{rngs::StdRng, Rng};
 use scan_fmt::scan_fmt;
 
-fn parse_move(lines: &[String], move_fn: fn(u32, u32, u32, &mut Vec<Vec<char>>) -> ()) -> String {
-    let mut states: Vec<Vec<char>> = Vec::new();
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
 
-    for line in lines {
-        if line.trim().starts_with('1') {
+/// The starting stacks of crates, bottom first, and the moves as `(count, from, to)`.
+pub struct Procedure {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<(u32, u32, u32)>,
+}
+
+fn parse_procedure(input: &Input) -> Procedure {
+    let mut stacks: Vec<Vec<char>> = Vec::new();
+    let mut moves = Vec::new();
+
+    for line in input.lines() {
+        if line.trim().starts_with('1') || line.is_empty() {
             // Header done
         } else if line.starts_with("move") {
             // Command
-            let (count, from, to) = scan_fmt!(line, "move {d} from {d} to {d}\n", u32, u32, u32).unwrap();
-
-            move_fn(count, from, to, &mut states);
+            moves.push(scan_fmt!(line, "move {d} from {d} to {d}", u32, u32, u32).unwrap());
         } else {
             // Header
             let sl: Vec<char> = line.chars().collect();
 
             for (i, column_crate) in sl.chunks(4).map(|c| c[1]).enumerate() {
-                if i >= states.len() {
-                    states.push(Vec::new());
+                if i >= stacks.len() {
+                    stacks.push(Vec::new());
                 }
                 if column_crate == ' ' {
                     continue;
                 }
-                states[i].insert(0, column_crate);
+                stacks[i].insert(0, column_crate);
             }
         }
     }
 
+    Procedure { stacks, moves }
+}
+
+fn rearrange(procedure: &Procedure, move_fn: fn(u32, u32, u32, &mut Vec<Vec<char>>)) -> String {
+    let mut states = procedure.stacks.clone();
+
+    for (count, from, to) in &procedure.moves {
+        move_fn(*count, *from, *to, &mut states);
+    }
+
     states.iter().map(|s| s.last().unwrap()).collect()
 }
 
-fn part1(lines: &[String]) -> String {
-    parse_move(lines, |count, from, to, states| {
+fn part1(procedure: &Procedure) -> String {
+    rearrange(procedure, |count, from, to, states| {
         for _ in 0..count {
             let e = states[(from - 1) as usize].pop().unwrap();
             states[(to - 1) as usize].push(e);
@@ -39,8 +57,8 @@ fn part1(lines: &[String]) -> String {
     })
 }
 
-fn part2(lines: &[String]) -> String {
-    parse_move(lines, |count, from, to, states| {
+fn part2(procedure: &Procedure) -> String {
+    rearrange(procedure, |count, from, to, states| {
         let mut removed = Vec::new();
         for _ in 0..count {
             let e = states[(from - 1) as usize].pop().unwrap();
@@ -53,28 +71,81 @@ fn part2(lines: &[String]) -> String {
     })
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day5.txt");
-    let day1_result = part1(&lines);
-    let day2_result = part2(&lines);
+/// Nine stacks of crates followed by `size` moves. Moves never take the last crate of a
+/// stack, as the answer is read from the top of every stack.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut stacks: Vec<Vec<char>> = (0..9)
+        .map(|i| {
+            // At least one stack has a crate to spare
+            let height = rng.gen_range(if i == 0 { 2 } else { 1 }..=8);
+            (0..height).map(|_| rng.gen_range('A'..='Z')).collect()
+        })
+        .collect();
+
+    let mut lines = Vec::new();
+    let height = stacks.iter().map(|s| s.len()).max().unwrap();
+    for level in (0..height).rev() {
+        let crates: Vec<_> = stacks
+            .iter()
+            .map(|s| {
+                s.get(level)
+                    .map_or("   ".to_string(), |c| format!("[{}]", c))
+            })
+            .collect();
+        lines.push(crates.join(" "));
+    }
+    let labels: Vec<_> = (1..=stacks.len()).map(|i| format!(" {} ", i)).collect();
+    lines.push(labels.join(" "));
+    lines.push(String::new());
+
+    for _ in 0..size.max(1) {
+        let from = loop {
+            let from = rng.gen_range(0..stacks.len());
+            if stacks[from].len() >= 2 {
+                break from;
+            }
+        };
+        let to = loop {
+            let to = rng.gen_range(0..stacks.len());
+            if to != from {
+                break to;
+            }
+        };
+        let count = rng.gen_range(1..stacks[from].len());
+
+        let at = stacks[from].len() - count;
+        let moved = stacks[from].split_off(at);
+        stacks[to].extend(moved);
+        lines.push(format!("move {} from {} to {}", count, from + 1, to + 1));
+    }
 
-    (day1_result, day2_result)
+    lines.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"    [D]    
-[N] [C]    
-[Z] [M] [P]
-    1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2",
-    );
-
-    assert_eq!("CMZ", part1(&lines));
-    assert_eq!("MCD", part2(&lines));
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Procedure;
+
+    const METADATA: Metadata = Metadata {
+        title: "Supply Stacks",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["parsing", "stacks"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_procedure(input)
+    }
+
+    fn part1(&self, procedure: &Self::Input) -> Answer {
+        part1(procedure).into()
+    }
+
+    fn part2(&self, procedure: &Self::Input) -> Answer {
+        part2(procedure).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
@@ -1,5 +1,9 @@
 use std::{cell::RefCell, rc::Rc};
 
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
 type FolderHandle = Rc<RefCell<Folder>>;
 
 #[derive(Default)]
@@ -31,24 +35,51 @@ impl Folder {
     }
 }
 
-fn build_tree(lines: &[String]) -> FolderHandle {
+/// A line of the terminal output. Directory and file names don't matter to the sizes.
+pub enum TerminalLine {
+    CdRoot,
+    CdUp,
+    CdInto,
+    Ls,
+    Dir,
+    File(u64),
+}
+
+fn parse_terminal(input: &Input) -> Vec<TerminalLine> {
+    input
+        .lines()
+        .map(|line| {
+            let args: Vec<&str> = line.split(' ').collect();
+            match args.as_slice() {
+                ["$", "cd", ".."] => TerminalLine::CdUp,
+                ["$", "cd", "/"] => TerminalLine::CdRoot,
+                ["$", "cd", _] => TerminalLine::CdInto,
+                ["$", "ls"] => TerminalLine::Ls,
+                ["dir", _] => TerminalLine::Dir,
+                [size_str, _] => TerminalLine::File(size_str.parse().unwrap()),
+                _ => panic!("Unknown command! {}", line),
+            }
+        })
+        .collect()
+}
+
+fn build_tree(terminal: &[TerminalLine]) -> FolderHandle {
     let root = Rc::new(RefCell::new(Folder::default()));
     let mut current = root.clone();
 
-    for line in lines.iter() {
-        let args: Vec<&str> = line.split(' ').collect();
-        match args.as_slice() {
-            ["$", "cd", ".."] => {
+    for line in terminal {
+        match line {
+            TerminalLine::CdUp => {
                 let parent = current.borrow().parent.clone();
                 match parent {
                     Some(parent) => current = parent,
                     _ => panic!("No parent path"),
                 }
             }
-            ["$", "cd", "/"] => {
+            TerminalLine::CdRoot => {
                 // Skip, this is the current node
             }
-            ["$", "cd", _] => {
+            TerminalLine::CdInto => {
                 let new_folder = Rc::new(RefCell::new(Folder {
                     parent: Some(current.clone()),
                     ..Default::default()
@@ -56,24 +87,22 @@ fn build_tree(lines: &[String]) -> FolderHandle {
                 current.borrow_mut().sub_folders.push(new_folder.clone());
                 current = new_folder;
             }
-            ["$", "ls"] => {
+            TerminalLine::Ls => {
                 // Wait for files on current
             }
-            ["dir", _] => {
+            TerminalLine::Dir => {
                 // Skip those
             }
-            [size_str, _] => {
-                let size: u64 = size_str.parse().unwrap();
+            TerminalLine::File(size) => {
                 current.borrow_mut().size += size;
             }
-            _ => panic!("Unknown command! {}", line),
         }
     }
     root
 }
 
-fn part1(lines: &[String]) -> u64 {
-    let tree = build_tree(lines);
+fn part1(terminal: &[TerminalLine]) -> u64 {
+    let tree = build_tree(terminal);
     let mut sum_of_total_sizes_over_100000 = 0;
 
     tree.borrow().visit_tree(&mut |folder| {
@@ -86,8 +115,8 @@ fn part1(lines: &[String]) -> u64 {
     sum_of_total_sizes_over_100000
 }
 
-fn part2(lines: &[String]) -> u64 {
-    let tree = build_tree(lines);
+fn part2(terminal: &[TerminalLine]) -> u64 {
+    let tree = build_tree(terminal);
     let total_size = tree.borrow().compute_total_size();
 
     let total_disk_space = 70000000;
@@ -95,7 +124,7 @@ fn part2(lines: &[String]) -> u64 {
     let space_needed_for_update = 30000000;
     let min_folder_size_must_be = space_needed_for_update - free_space;
 
-    let mut smallest_yet_large_enough_size_found = std::u64::MAX;
+    let mut smallest_yet_large_enough_size_found = u64::MAX;
 
     tree.borrow().visit_tree(&mut |folder| {
         let total_size_of_subfolder_tree = folder.compute_total_size();
@@ -109,42 +138,100 @@ fn part2(lines: &[String]) -> u64 {
     smallest_yet_large_enough_size_found
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day7.txt");
-    let result_1 = part1(&lines);
-    let result_2 = part2(&lines);
+/// A terminal transcript exploring a tree of about `size` directories, using more than the
+/// 40000000 bytes part 2 needs to free space from but less than the 70000000 byte disk.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    // Files shrink as directories are added, so that even with every directory holding
+    // its five files of the largest size, the tree stays under 60000000 bytes
+    let max_file_size = (60000000 / ((size as u64 + 1) * 5)).clamp(1, 300000);
+
+    let mut lines = vec!["$ cd /".to_string()];
+    let mut remaining = size;
+    let used = generate_directory(rng, &mut lines, &mut remaining, max_file_size, 0);
+
+    if used <= 40000000 {
+        // Right after the root's `$ ls`
+        let padding = 40000000 - used + rng.gen_range(1..=10000000);
+        lines.insert(2, format!("{} padding.dat", padding));
+    }
+
+    lines.join("\n")
+}
+
+fn generate_directory(
+    rng: &mut StdRng,
+    lines: &mut Vec<String>,
+    remaining: &mut usize,
+    max_file_size: u64,
+    depth: usize,
+) -> u64 {
+    let name = |rng: &mut StdRng| -> String {
+        (0..rng.gen_range(1..=8))
+            .map(|_| rng.gen_range('a'..='z'))
+            .collect()
+    };
+
+    let directories = if depth < 12 {
+        rng.gen_range(1..=4).min(*remaining)
+    } else {
+        0
+    };
+    *remaining -= directories;
+
+    // `cd` into a repeated name would be ambiguous, so names are unique within a directory
+    let mut names: Vec<String> = Vec::new();
+    while names.len() < directories {
+        let name = name(rng);
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    lines.push("$ ls".to_string());
+    for directory in &names {
+        lines.push(format!("dir {}", directory));
+    }
+    let mut used = 0;
+    for _ in 0..rng.gen_range(0..=5) {
+        let file = format!("{}.{}", name(rng), name(rng));
+        let file_size = rng.gen_range((max_file_size / 300).max(1)..=max_file_size);
+        used += file_size;
+        lines.push(format!("{} {}", file_size, file));
+    }
+
+    for directory in names {
+        lines.push(format!("$ cd {}", directory));
+        used += generate_directory(rng, lines, remaining, max_file_size, depth + 1);
+        lines.push("$ cd ..".to_string());
+    }
 
-    (result_1.to_string(), result_2.to_string())
+    used
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k",
-    );
-
-    assert_eq!(95437, part1(&lines));
-    assert_eq!(24933642, part2(&lines));
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<TerminalLine>;
+
+    const METADATA: Metadata = Metadata {
+        title: "No Space Left On Device",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["parsing", "tree"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        parse_terminal(input)
+    }
+
+    fn part1(&self, terminal: &Self::Input) -> Answer {
+        part1(terminal).into()
+    }
+
+    fn part2(&self, terminal: &Self::Input) -> Answer {
+        part2(terminal).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
@@ -1,7 +1,10 @@
 use std::collections::HashSet;
 
-fn part1(lines: &[String]) -> usize {
-    let rows_cols: Vec<Vec<char>> = lines.iter().map(|row| row.chars().collect()).collect();
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
+fn part1(rows_cols: &[Vec<char>]) -> usize {
     let mut trees_visible: HashSet<(usize, usize)> = HashSet::new();
 
     // Check each row
@@ -52,9 +55,7 @@ fn part1(lines: &[String]) -> usize {
     trees_visible.len()
 }
 
-fn part2(lines: &[String]) -> u64 {
-    let rows_cols: Vec<Vec<char>> = lines.iter().map(|row| row.chars().collect()).collect();
-
+fn part2(rows_cols: &[Vec<char>]) -> u64 {
     let mut highest_score = 0;
 
     for (row_index, row) in rows_cols.iter().enumerate() {
@@ -79,9 +80,9 @@ fn part2(lines: &[String]) -> u64 {
 
             // Down
             let mut num_trees_visible_down = 0;
-            for visit_row in (row_index + 1)..rows_cols.len() {
+            for visit_row in rows_cols.iter().skip(row_index + 1) {
                 num_trees_visible_down += 1;
-                if rows_cols[visit_row][col_index] >= current_height {
+                if visit_row[col_index] >= current_height {
                     break;
                 }
             }
@@ -97,10 +98,9 @@ fn part2(lines: &[String]) -> u64 {
 
             // Right
             let mut num_trees_visible_right = 0;
-            let row_len = row.len();
-            for visit_col in (col_index + 1)..row_len {
+            for visit_col in row.iter().skip(col_index + 1) {
                 num_trees_visible_right += 1;
-                if rows_cols[row_index][visit_col] >= current_height {
+                if *visit_col >= current_height {
                     break;
                 }
             }
@@ -119,24 +119,125 @@ fn part2(lines: &[String]) -> u64 {
     highest_score
 }
 
-pub fn run() -> (String, String) {
-    let lines = crate::aoc::lines_from_file("day8.txt");
-    let result_1 = part1(&lines);
-    let result_2 = part2(&lines);
+/// A `size` by `size` grid of tree heights.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let rows: Vec<String> = (0..size.max(1))
+        .map(|_| (0..size.max(1)).map(|_| rng.gen_range('0'..='9')).collect())
+        .collect();
+
+    rows.join("\n")
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Vec<char>>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Treetop Tree House",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["grid"],
+    };
+
+    fn parse(&self, input: &Input) -> Self::Input {
+        input.char_grid()
+    }
+
+    fn part1(&self, trees: &Self::Input) -> Answer {
+        part1(trees).into()
+    }
+
+    fn part2(&self, trees: &Self::Input) -> Answer {
+        part2(trees).into()
+    }
 
-    (result_1.to_string(), result_2.to_string())
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"30373
-25512
-65332
-33549
-35390",
-    );
-
-    assert_eq!(21, part1(&lines));
-    assert_eq!(8, part2(&lines));
+#[cfg(test)]
+mod differential_tests {
+    use rand::Rng;
+
+    use super::{part1, part2};
+    use crate::differential;
+
+    /// Walks out from every tree in all four directions, one tree at a time.
+    fn naive(trees: &[Vec<char>]) -> (usize, u64) {
+        let mut visible = 0;
+        let mut highest_score = 0;
+
+        for (y, row) in trees.iter().enumerate() {
+            for (x, &height) in row.iter().enumerate() {
+                let lines_of_sight: [Vec<char>; 4] = [
+                    (0..y).rev().map(|y| trees[y][x]).collect(),
+                    (y + 1..trees.len()).map(|y| trees[y][x]).collect(),
+                    (0..x).rev().map(|x| row[x]).collect(),
+                    (x + 1..row.len()).map(|x| row[x]).collect(),
+                ];
+
+                if lines_of_sight
+                    .iter()
+                    .any(|line| line.iter().all(|&t| t < height))
+                {
+                    visible += 1;
+                }
+
+                let score: u64 = lines_of_sight
+                    .iter()
+                    .map(|line| match line.iter().position(|&t| t >= height) {
+                        Some(blocker) => blocker as u64 + 1,
+                        None => line.len() as u64,
+                    })
+                    .product();
+                highest_score = highest_score.max(score);
+            }
+        }
+
+        (visible, highest_score)
+    }
+
+    #[test]
+    fn test_visibility_and_scenic_score_match_naive() {
+        differential::check(
+            |rng| {
+                let (width, height) = (rng.gen_range(1..=6), rng.gen_range(1..=6));
+                (0..height)
+                    .map(|_| (0..width).map(|_| rng.gen_range('0'..='4')).collect())
+                    .collect::<Vec<Vec<char>>>()
+            },
+            |trees| {
+                let mut candidates = Vec::new();
+                if trees.len() > 1 {
+                    for y in 0..trees.len() {
+                        let mut fewer_rows = trees.clone();
+                        fewer_rows.remove(y);
+                        candidates.push(fewer_rows);
+                    }
+                }
+                if trees[0].len() > 1 {
+                    for x in 0..trees[0].len() {
+                        let mut fewer_cols = trees.clone();
+                        fewer_cols.iter_mut().for_each(|row| {
+                            row.remove(x);
+                        });
+                        candidates.push(fewer_cols);
+                    }
+                }
+                for (y, row) in trees.iter().enumerate() {
+                    for (x, &tree) in row.iter().enumerate() {
+                        if tree > '0' {
+                            let mut lower = trees.clone();
+                            lower[y][x] = (tree as u8 - 1) as char;
+                            candidates.push(lower);
+                        }
+                    }
+                }
+                candidates
+            },
+            |trees| (part1(trees), part2(trees)),
+            |trees| naive(trees),
+        );
+    }
 }
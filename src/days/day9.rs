@@ -1,5 +1,9 @@
 use std::{collections::HashSet, fmt::Debug, str::FromStr};
 
+use rand::{rngs::StdRng, Rng};
+
+use crate::aoc::{Answer, Input, Metadata, PartStatus, Solution};
+
 type GridPosition = (i32, i32);
 
 enum Direction {
@@ -89,7 +93,7 @@ impl Rope {
     }
 }
 
-struct MoveInstruction {
+pub struct MoveInstruction {
     direction: Direction,
     num_steps: u32,
 }
@@ -135,35 +139,45 @@ fn part2(instructions: &[MoveInstruction]) -> usize {
     rope.tail_locations_visited.len()
 }
 
-pub fn run() -> (String, String) {
-    let instructions: Vec<MoveInstruction> = crate::aoc::lines_from_file("day9.txt")
-        .iter()
-        .map(|line| line.parse().expect("Failed to parse instruction"))
+/// `size` head motions of up to 20 steps.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let motions: Vec<_> = (0..size.max(1))
+        .map(|_| {
+            let direction = ["R", "U", "L", "D"][rng.gen_range(0..4)];
+            format!("{} {}", direction, rng.gen_range(1..=20))
+        })
         .collect();
 
-    let result_1 = part1(&instructions);
-    let result_2 = part2(&instructions);
-
-    (result_1.to_string(), result_2.to_string())
+    motions.join("\n")
 }
 
-#[test]
-fn it_works() {
-    let lines: Vec<String> = crate::aoc::lines_from_test(
-        r"R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2",
-    );
-    let instructions: Vec<MoveInstruction> = lines
-        .iter()
-        .map(|line| line.parse().expect("Failed to parse instruction"))
-        .collect();
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<MoveInstruction>;
+
+    const METADATA: Metadata = Metadata {
+        title: "Rope Bridge",
+        parts: [PartStatus::Solved, PartStatus::Solved],
+        tags: &["grid", "simulation"],
+    };
 
-    assert_eq!(13, part1(&instructions));
-    assert_eq!(1, part2(&instructions));
+    fn parse(&self, input: &Input) -> Self::Input {
+        input
+            .lines()
+            .map(|line| line.parse().expect("Failed to parse instruction"))
+            .collect()
+    }
+
+    fn part1(&self, instructions: &Self::Input) -> Answer {
+        part1(instructions).into()
+    }
+
+    fn part2(&self, instructions: &Self::Input) -> Answer {
+        part2(instructions).into()
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
@@ -1,43 +1,45 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
 
 use counted_array::counted_array;
 
+use crate::aoc::AnySolution;
+
 counted_array!(
-    pub const ALL_DAYS: [fn() -> (String, String); _] = [
-        day1::run,
-        day2::run,
-        day3::run,
-        day4::run,
-        day5::run,
-        day6::run,
-        day7::run,
-        day8::run,
-        day9::run,
-        day10::run,
-        day11::run,
-        day12::run,
-        day13::run,
-        day14::run,
-        day15::run,
-        day16::run,
-        day17::run,
-        day18::run
+    pub const ALL_DAYS: [&dyn AnySolution; _] = [
+        &day1::Day1,
+        &day2::Day2,
+        &day3::Day3,
+        &day4::Day4,
+        &day5::Day5,
+        &day6::Day6,
+        &day7::Day7,
+        &day8::Day8,
+        &day9::Day9,
+        &day10::Day10,
+        &day11::Day11,
+        &day12::Day12,
+        &day13::Day13,
+        &day14::Day14,
+        &day15::Day15,
+        &day16::Day16,
+        &day17::Day17,
+        &day18::Day18
     ]
 );
@@ -0,0 +1,75 @@
+//! Differential testing: runs an optimised solver and a naive reference implementation
+//! on many small random inputs and, when they disagree, shrinks the input to the smallest
+//! one that still shows the difference.
+
+use std::{
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Number of random inputs each differential test tries.
+pub const CASES: u64 = 500;
+
+/// Compares `solver` against `reference` on [`CASES`] inputs from `generate`, each seeded
+/// with its case number so a failure reproduces. On a difference or a panic, repeatedly
+/// replaces the input with the first candidate from `shrink` that still fails, then panics
+/// with that smallest failing input.
+pub fn check<T, A>(
+    generate: impl Fn(&mut StdRng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    solver: impl Fn(&T) -> A,
+    reference: impl Fn(&T) -> A,
+) where
+    T: Debug,
+    A: Debug + PartialEq,
+{
+    let failure = |input: &T| {
+        match panic::catch_unwind(AssertUnwindSafe(|| (solver(input), reference(input)))) {
+            Ok((answer, expected)) if answer == expected => None,
+            Ok((answer, expected)) => Some(format!(
+                "solver gave {:?}, reference gave {:?}",
+                answer, expected
+            )),
+            Err(_) => Some("panicked".to_string()),
+        }
+    };
+
+    for case in 0..CASES {
+        let mut input = generate(&mut StdRng::seed_from_u64(case));
+        let Some(mut reason) = failure(&input) else {
+            continue;
+        };
+
+        while let Some((smaller, smaller_reason)) = shrink(&input)
+            .into_iter()
+            .find_map(|candidate| failure(&candidate).map(|reason| (candidate, reason)))
+        {
+            input = smaller;
+            reason = smaller_reason;
+        }
+
+        panic!(
+            "Case {} failed: {}\nSmallest failing input: {:?}",
+            case, reason, input
+        );
+    }
+}
+
+/// Shrink candidates for a list: each half, then the list without each of its items.
+pub fn shrink_vec<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+    if items.len() > 1 {
+        let (left, right) = items.split_at(items.len() / 2);
+        candidates.push(left.to_vec());
+        candidates.push(right.to_vec());
+    }
+    for i in 0..items.len() {
+        let mut candidate = items.to_vec();
+        candidate.remove(i);
+        candidates.push(candidate);
+    }
+
+    candidates
+}
@@ -0,0 +1,77 @@
+use std::{fmt::Display, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum AocError {
+    /// A year without registered days was requested.
+    InvalidYear(u16),
+    /// A day outside of the registered days was requested.
+    InvalidDay { year: u16, day: usize, max: usize },
+    /// An input was requested from a day without an input generator.
+    NoGenerator { year: u16, day: usize },
+    /// A puzzle input wasn't found where it was looked for.
+    InputNotFound(PathBuf),
+    /// A puzzle input couldn't be read.
+    Input { path: String, source: io::Error },
+    /// The answers file couldn't be read, parsed or written.
+    Answers(String),
+    /// A new day couldn't be scaffolded.
+    Scaffold(String),
+    /// The run history couldn't be read or written.
+    History(String),
+    /// `verify` found answers that differ from the recorded ones.
+    Mismatches(usize),
+}
+
+impl AocError {
+    /// Process exit code for the error, distinct per kind of failure.
+    /// Clap uses 2 for usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AocError::Mismatches(_) => 1,
+            AocError::InvalidYear(_)
+            | AocError::InvalidDay { .. }
+            | AocError::NoGenerator { .. } => 3,
+            AocError::InputNotFound(_) | AocError::Input { .. } => 4,
+            AocError::Answers(_) => 5,
+            AocError::Scaffold(_) => 6,
+            AocError::History(_) => 7,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::InvalidYear(year) => write!(f, "No days registered for {}", year),
+            AocError::InvalidDay { year, day, max } => {
+                write!(
+                    f,
+                    "Day {} doesn't exist for {}, expected a day from 1 to {}",
+                    day, year, max
+                )
+            }
+            AocError::NoGenerator { year, day } => {
+                write!(f, "Day {} of {} has no input generator", day, year)
+            }
+            AocError::InputNotFound(path) => {
+                write!(f, "Input not found: {}", path.display())
+            }
+            AocError::Input { path, source } => {
+                write!(f, "Failed to read input '{}': {}", path, source)
+            }
+            AocError::Answers(message) => write!(f, "Answers file: {}", message),
+            AocError::Scaffold(message) => write!(f, "Failed to create day: {}", message),
+            AocError::History(message) => write!(f, "Run history: {}", message),
+            AocError::Mismatches(count) => write!(f, "{} answer(s) didn't match", count),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Input { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
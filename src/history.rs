@@ -0,0 +1,253 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    aoc::PartStatus,
+    error::AocError,
+    runner::{self, DayResult, Status},
+    years::{self, Year},
+};
+
+/// The run history in the source tree, one JSON record per line for each part run.
+pub fn history_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("history.jsonl")
+}
+
+/// A part's answer and timing from one recorded run.
+pub struct Entry {
+    /// When the run started, in milliseconds since the Unix epoch. Shared by every part
+    /// recorded by the same run.
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: usize,
+    pub part: usize,
+    /// The part's answer, or `None` if it didn't finish.
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+}
+
+/// Appends the parts of `results` to the history file as a single run.
+pub fn record(results: &[DayResult]) -> Result<(), AocError> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+
+    let mut lines = String::new();
+    for r in results {
+        // Stubs, parts known to be wrong and days that couldn't run have nothing to compare
+        // between runs
+        let statuses = years::get(r.year)
+            .and_then(|year| year.solution(r.day))
+            .map_or([PartStatus::Solved; 2], |solution| {
+                solution.metadata().parts
+            });
+        let parts = r.parts().filter(|(part, result)| {
+            statuses[part - 1] != PartStatus::Wrong
+                && !matches!(result.status, Status::NotImplemented | Status::Failed)
+        });
+        for (part, result) in parts {
+            let record = serde_json::json!({
+                "timestamp": timestamp,
+                "year": r.year,
+                "day": r.day,
+                "part": part,
+                "answer": result.answer.as_ref().map(|a| a.to_string()),
+                "duration_ns": result.elapsed.as_nanos() as u64,
+                "status": result.status.to_string(),
+            });
+            lines.push_str(&record.to_string());
+            lines.push('\n');
+        }
+    }
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_path())
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(|e| AocError::History(e.to_string()))
+}
+
+/// Loads every recorded entry, oldest first. A missing history file has no entries.
+pub fn load() -> Result<Vec<Entry>, AocError> {
+    let contents = match fs::read_to_string(history_path()) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(AocError::History(e.to_string())),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            parse_entry(line)
+                .ok_or_else(|| AocError::History(format!("Invalid record on line {}", i + 1)))
+        })
+        .collect()
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let record: serde_json::Value = serde_json::from_str(line).ok()?;
+    let number = |key: &str| record.get(key)?.as_u64();
+
+    Some(Entry {
+        timestamp: number("timestamp")?,
+        year: number("year")?.try_into().ok()?,
+        day: number("day")? as usize,
+        part: number("part").filter(|part| (1..=2).contains(part))? as usize,
+        answer: record
+            .get("answer")
+            .and_then(|a| a.as_str())
+            .map(|a| a.to_string()),
+        elapsed: Duration::from_nanos(number("duration_ns")?),
+    })
+}
+
+/// Formats milliseconds since the Unix epoch as a UTC date and time.
+fn format_timestamp(timestamp: u64) -> String {
+    let secs = timestamp / 1000;
+    let (days, time) = ((secs / 86400) as i64, secs % 86400);
+
+    // Civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Prints every recorded run of a day, comparing each part with the previous run that
+/// finished it. Flags answers that changed and parts that got more than `threshold`
+/// percent slower.
+pub fn print_history(year: &Year, day: usize, threshold: f64) -> Result<(), AocError> {
+    year.solution(day)?;
+    let entries: Vec<_> = load()?
+        .into_iter()
+        .filter(|e| e.year == year.year && e.day == day)
+        .collect();
+
+    if entries.is_empty() {
+        println!("No recorded runs of day {}", day);
+        return Ok(());
+    }
+
+    let mut runs: Vec<(u64, [Option<&Entry>; 2])> = Vec::new();
+    for entry in &entries {
+        if runs.last().map(|(timestamp, _)| *timestamp) != Some(entry.timestamp) {
+            runs.push((entry.timestamp, [None, None]));
+        }
+        runs.last_mut().unwrap().1[entry.part - 1] = Some(entry);
+    }
+
+    let mut previous: [Option<&Entry>; 2] = [None, None];
+    let mut rows = Vec::new();
+    for (timestamp, parts) in &runs {
+        let mut row = vec![format_timestamp(*timestamp)];
+        let mut flags = Vec::new();
+
+        for (part, (entry, previous)) in parts.iter().zip(previous.iter_mut()).enumerate() {
+            let Some(entry) = entry else {
+                row.extend(["-".to_string(), "-".to_string()]);
+                continue;
+            };
+            let Some(answer) = &entry.answer else {
+                row.extend(["-".to_string(), "-".to_string()]);
+                flags.push(format!("part {} didn't finish", part + 1));
+                continue;
+            };
+
+            let mut time = format!("{:.2?}", entry.elapsed);
+            if let Some(previous) = previous {
+                if previous.answer.as_ref() != Some(answer) {
+                    flags.push(format!("part {} answer changed", part + 1));
+                }
+
+                let change =
+                    (entry.elapsed.as_secs_f64() / previous.elapsed.as_secs_f64() - 1.0) * 100.0;
+                if change.is_finite() {
+                    time = format!("{} ({:+.0}%)", time, change);
+                    if change > threshold {
+                        flags.push(format!("part {} {:.0}% slower", part + 1, change));
+                    }
+                }
+            }
+
+            row.extend([answer.clone(), time]);
+            *previous = Some(entry);
+        }
+
+        row.push(flags.join(", "));
+        rows.push(row);
+    }
+
+    println!("Day {}", day);
+    runner::print_aligned(
+        &[
+            "Run",
+            "Part 1",
+            "Part 1 time",
+            "Part 2",
+            "Part 2 time",
+            "Flags",
+        ],
+        &[true, true, false, true, false, true],
+        &rows,
+        false,
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod history_tests {
+    use super::{format_timestamp, parse_entry};
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!("1970-01-01 00:00:00", format_timestamp(0));
+        assert_eq!("2022-12-01 05:00:00", format_timestamp(1_669_870_800_000));
+        assert_eq!("2024-02-29 23:59:59", format_timestamp(1_709_251_199_000));
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        let entry = parse_entry(
+            r#"{"answer":"24000","day":1,"duration_ns":1500,"part":1,"status":"ok","timestamp":42,"year":2022}"#,
+        )
+        .unwrap();
+
+        assert_eq!(42, entry.timestamp);
+        assert_eq!((2022, 1, 1), (entry.year, entry.day, entry.part));
+        assert_eq!(Some("24000"), entry.answer.as_deref());
+        assert_eq!(1500, entry.elapsed.as_nanos());
+
+        assert!(parse_entry(r#"{"day":1}"#).is_none());
+        for part in [0, 3] {
+            let line = format!(
+                r#"{{"answer":"24000","day":1,"duration_ns":1500,"part":{},"timestamp":42,"year":2022}}"#,
+                part
+            );
+            assert!(parse_entry(&line).is_none());
+        }
+    }
+}
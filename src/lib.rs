@@ -0,0 +1,16 @@
+//! Advent of Code solutions, with the day registry, input helpers and runner used by
+//! the `aoc2022` binary.
+
+pub mod answers;
+pub mod aoc;
+pub mod bench;
+pub mod days;
+#[cfg(test)]
+mod differential;
+pub mod error;
+pub mod history;
+pub mod memory;
+pub mod runner;
+pub mod scaffold;
+pub mod watch;
+pub mod years;
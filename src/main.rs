@@ -1,21 +1,357 @@
-mod aoc;
-mod days;
+use std::time::Duration;
 
-use clap::Parser;
+use aoc2022::{
+    answers::{self, Answers},
+    aoc::{self, PartStatus, Verbosity},
+    bench,
+    error::AocError,
+    history,
+    runner::{self, DaySpec, Format, Parts, Status},
+    scaffold, watch,
+    years::{self, Year},
+};
+use clap::{error::ErrorKind, ArgAction, CommandFactory, Parser, Subcommand};
+use rand::{rngs::StdRng, SeedableRng};
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct DaySelection {
-    day: usize
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Event year to run the days of
+    #[arg(long, global = true, default_value_t = years::LATEST_YEAR)]
+    year: u16,
+
+    /// Give up on a day after this many seconds and report it as timed out
+    #[arg(long, global = true, value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+
+    /// Print the solvers' visual output such as grids, and their debug output with `-vv`
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Only print results and errors
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Day to run: a number, `all`, or a range such as `1..=10`
+    #[arg(required = true)]
+    day: Option<DaySpec>,
+
+    /// Read the puzzle input from this file instead of `inputs/YEAR/dayNN.txt`, or `-` for stdin
+    #[arg(long)]
+    input: Option<String>,
+
+    /// Which parts to run
+    #[arg(long, value_enum, default_value_t = Parts::Both)]
+    part: Parts,
+
+    /// Output format for the results
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Number of days to run at once
+    #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check answers against the ones recorded in `answers.toml`
+    Verify {
+        /// Days to verify: a number, `all`, or a range such as `1..=10`
+        #[arg(default_value = "all")]
+        day: DaySpec,
+
+        /// Record the current answers instead of comparing against them
+        #[arg(long)]
+        record: bool,
+    },
+    /// Time parse, part 1 and part 2 over many iterations, excluding input reading
+    Bench {
+        /// Days to benchmark: a number, `all`, or a range such as `1..=10`
+        day: DaySpec,
+
+        /// Number of timed iterations
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+
+        /// Number of untimed iterations to run first
+        #[arg(long, default_value_t = 1)]
+        warmup: u32,
+    },
+    /// Re-run a day whenever its input file changes
+    Watch {
+        /// The day to watch
+        day: usize,
+
+        /// Watch this file instead of `inputs/YEAR/dayNN.txt`
+        #[arg(long)]
+        input: Option<String>,
+
+        /// How often to check the input for changes, in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+    /// Show the recorded runs of a day, flagging changed answers and slowdowns
+    History {
+        /// The day to show
+        day: usize,
+
+        /// Flag parts that got more than this many percent slower than the previous run
+        #[arg(long, default_value_t = 20.0)]
+        threshold: f64,
+    },
+    /// Print a random input for a day, the same for the same seed and size
+    Gen {
+        /// The day to generate an input for
+        day: usize,
+
+        /// Seed for the random number generator
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Roughly how many items (lines, elves, monkeys...) to generate
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+    },
+    /// List the year's days with their titles, tags and how far along each part is
+    List,
+    /// Create `dayN.rs` in the year's module and `dayNN.txt` from the blank day template
+    NewDay {
+        /// The day to add, which must be the next unregistered day
+        day: usize,
+    },
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("'{}' isn't a positive number of seconds", s))
 }
 
 fn main() {
-    let day = DaySelection::parse().day - 1;
+    if let Err(e) = try_main(DaySelection::parse()) {
+        eprintln!("error: {}", e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+fn try_main(selection: DaySelection) -> Result<(), AocError> {
+    aoc::set_verbosity(match (selection.quiet, selection.verbose) {
+        (true, _) => Verbosity::Quiet,
+        (false, 0) => Verbosity::Normal,
+        (false, 1) => Verbosity::Verbose,
+        (false, _) => Verbosity::Debug,
+    });
+    let year = years::get(selection.year)?;
+
+    match selection.command {
+        Some(Command::Verify { day, record }) => verify(year, &day, record, selection.timeout),
+        Some(Command::Bench {
+            day,
+            iterations,
+            warmup,
+        }) => {
+            day.validate(year)?;
+            for day in day.days(year) {
+                bench::print_bench(&bench::bench_day(year, day, iterations, warmup)?);
+            }
+            Ok(())
+        }
+        Some(Command::Watch {
+            day,
+            input,
+            interval,
+        }) => {
+            if input.as_deref() == Some("-") {
+                DaySelection::command()
+                    .error(ErrorKind::InvalidValue, "can't watch stdin")
+                    .exit();
+            }
+            watch::watch(
+                year,
+                day,
+                input.as_deref(),
+                Duration::from_millis(interval),
+                selection.timeout,
+            )
+        }
+        Some(Command::History { day, threshold }) => history::print_history(year, day, threshold),
+        Some(Command::Gen { day, seed, size }) => {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let input =
+                year.solution(day)?
+                    .generate(&mut rng, size)
+                    .ok_or(AocError::NoGenerator {
+                        year: year.year,
+                        day,
+                    })?;
+            println!("{}", input);
+            Ok(())
+        }
+        Some(Command::List) => {
+            list(year);
+            Ok(())
+        }
+        Some(Command::NewDay { day }) => scaffold::new_day(year, day),
+        None => run(
+            year,
+            &selection.day.unwrap(),
+            selection.input.as_deref(),
+            selection.part,
+            selection.format,
+            selection.timeout,
+            selection.jobs as usize,
+        ),
+    }
+}
+
+fn run(
+    year: &'static Year,
+    day: &DaySpec,
+    input: Option<&str>,
+    parts: Parts,
+    format: Format,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Result<(), AocError> {
+    if input.is_some() && !day.is_single() {
+        DaySelection::command()
+            .error(ErrorKind::ArgumentConflict, "--input requires a single day")
+            .exit();
+    }
+
+    day.validate(year)?;
+    let days: Vec<_> = day.days(year).collect();
+    let results = runner::run_days(year, &days, input, parts, timeout, jobs);
+
+    runner::print_results(&results, format);
+
+    // Answers for other inputs would show up as changes in the history
+    if input.is_none() {
+        if let Err(e) = history::record(&results) {
+            eprintln!("warning: {}", e);
+        }
+    }
 
-    let now = std::time::Instant::now();
-    
-    let result = days::ALL_DAYS[day]();
+    // The other days' results are out, so fail with the first day that couldn't run
+    match results.into_iter().find_map(|r| r.error) {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+fn verify(
+    year: &'static Year,
+    day: &DaySpec,
+    record: bool,
+    timeout: Option<Duration>,
+) -> Result<(), AocError> {
+    day.validate(year)?;
+    let mut answers = Answers::load()?;
+    let mut mismatches = Vec::new();
+
+    for day in day.days(year) {
+        let result = match runner::run_day(year, day, None, Parts::Both, timeout) {
+            Ok(result) => result,
+            Err(e) => {
+                println!("Day {}: FAILED", day);
+                eprintln!("error: {}", e);
+                if !record {
+                    mismatches.push(format!("Day {}: failed to run", day));
+                }
+                continue;
+            }
+        };
+
+        let statuses = year.solution(day)?.metadata().parts;
+        for (part, p) in result.parts() {
+            if p.status == Status::NotImplemented {
+                println!("Day {} part {}: NOT IMPLEMENTED", day, part);
+                continue;
+            }
+            // Known wrong answers would only be compared against other wrong answers
+            if statuses[part - 1] == PartStatus::Wrong {
+                println!("Day {} part {}: WRONG (not recorded)", day, part);
+                continue;
+            }
+            if p.status != Status::Ok {
+                println!(
+                    "Day {} part {}: {}",
+                    day,
+                    part,
+                    p.status.to_string().to_uppercase()
+                );
+                if !record {
+                    mismatches.push(format!("Day {} part {}: {}", day, part, p.status));
+                }
+                continue;
+            }
+
+            let answer = p.answer.as_ref().map_or(String::new(), |a| a.to_string());
+            if record {
+                // Pictures start on their own line to keep their rows lined up
+                let separator = if answer.contains('\n') { "\n" } else { " " };
+                println!("Day {} part {}:{}{}", day, part, separator, answer);
+                answers.set(year.year, day, part, answer);
+                continue;
+            }
+
+            match answers.get(year.year, day, part) {
+                Some(expected) if expected == answer => {
+                    println!("Day {} part {}: ok", day, part);
+                }
+                Some(expected) => {
+                    println!("Day {} part {}: MISMATCH", day, part);
+                    mismatches.push(format!(
+                        "Day {} part {}: expected {:?}, got {:?}",
+                        day, part, expected, answer
+                    ));
+                }
+                None => println!("Day {} part {}: no recorded answer", day, part),
+            }
+        }
+    }
+
+    if record {
+        answers.save()?;
+        aoc2022::info!("Recorded answers in {}", answers::answers_path().display());
+    } else if !mismatches.is_empty() {
+        eprintln!();
+        for mismatch in &mismatches {
+            eprintln!("  {}", mismatch);
+        }
+        return Err(AocError::Mismatches(mismatches.len()));
+    }
+
+    Ok(())
+}
 
-    let elapsed = now.elapsed();
+fn list(year: &Year) {
+    let headers = ["Day", "Title", "Part 1", "Part 2", "Tags"];
+    let rows: Vec<_> = year
+        .days
+        .iter()
+        .enumerate()
+        .map(|(i, solution)| {
+            let metadata = solution.metadata();
+            let part = |status: PartStatus| match status {
+                PartStatus::Stub => "NOT IMPLEMENTED".to_string(),
+                status => status.to_string(),
+            };
+            vec![
+                (i + 1).to_string(),
+                metadata.title.to_string(),
+                part(metadata.parts[0]),
+                part(metadata.parts[1]),
+                metadata.tags.join(", "),
+            ]
+        })
+        .collect();
 
-    println!("Completed in {:?}. Result: {:?}", elapsed, result);
+    runner::print_aligned(&headers, &[false, true, true, true, true], &rows, false);
 }
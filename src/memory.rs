@@ -0,0 +1,150 @@
+//! Heap usage of the current thread, measured by a counting global allocator that's only
+//! installed with the `alloc-stats` feature. Counters are kept per thread so days running
+//! alongside each other, or a timed out day still running, don't skew each other.
+
+pub struct MemoryStats {
+    /// Most bytes held at once since the last [`reset`].
+    pub peak: usize,
+    /// Number of allocations and reallocations since the last [`reset`].
+    pub allocations: u64,
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    pub struct Counters {
+        pub current: Cell<isize>,
+        pub peak: Cell<usize>,
+        pub allocations: Cell<u64>,
+    }
+
+    thread_local! {
+        pub static COUNTERS: Counters = const {
+            Counters {
+                current: Cell::new(0),
+                peak: Cell::new(0),
+                allocations: Cell::new(0),
+            }
+        };
+    }
+
+    fn record(change: isize, allocation: bool) {
+        // The counters may already be gone while a thread is shutting down
+        let _ = COUNTERS.try_with(|c| {
+            let current = c.current.get() + change;
+            c.current.set(current);
+            c.peak.set(c.peak.get().max(current.max(0) as usize));
+            if allocation {
+                c.allocations.set(c.allocations.get() + 1);
+            }
+        });
+    }
+
+    pub struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(layout.size() as isize, true);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record(layout.size() as isize, true);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record(-(layout.size() as isize), false);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = System.realloc(ptr, layout, new_size);
+            if !new.is_null() {
+                record(new_size as isize - layout.size() as isize, true);
+            }
+            new
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+}
+
+/// Starts measuring the current thread's heap usage from zero.
+pub fn reset() {
+    #[cfg(feature = "alloc-stats")]
+    counting::COUNTERS.with(|c| {
+        c.current.set(0);
+        c.peak.set(0);
+        c.allocations.set(0);
+    });
+}
+
+/// The current thread's heap usage since the last [`reset`], or `None` when built without
+/// the `alloc-stats` feature.
+pub fn stats() -> Option<MemoryStats> {
+    #[cfg(feature = "alloc-stats")]
+    return Some(counting::COUNTERS.with(|c| MemoryStats {
+        peak: c.peak.get(),
+        allocations: c.allocations.get(),
+    }));
+
+    #[cfg(not(feature = "alloc-stats"))]
+    None
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.50 MiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.2} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod memory_tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!("512 B", format_bytes(512));
+        assert_eq!("1.50 KiB", format_bytes(1536));
+        assert_eq!("3.00 MiB", format_bytes(3 * 1024 * 1024));
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn test_stats() {
+        reset();
+        let mut v: Vec<u8> = Vec::with_capacity(1000);
+        v.extend([1; 1000]);
+        drop(v);
+        let small = vec![0u8; 10];
+
+        let stats = stats().unwrap();
+        assert_eq!(1000, stats.peak);
+        assert_eq!(2, stats.allocations);
+        drop(small);
+    }
+}
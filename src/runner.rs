@@ -0,0 +1,660 @@
+use std::{
+    fmt::Display,
+    ops::RangeInclusive,
+    path::Path,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+
+use crate::{
+    aoc::{self, Answer, Input, PartStatus},
+    error::AocError,
+    memory::{self, MemoryStats},
+    years::Year,
+};
+
+/// Which days to run: a single day, `all`, or a range such as `1..=10` or `3..7`.
+#[derive(Clone, Debug)]
+pub enum DaySpec {
+    All,
+    Range(RangeInclusive<usize>),
+}
+
+impl DaySpec {
+    pub fn days(&self, year: &Year) -> RangeInclusive<usize> {
+        match self {
+            DaySpec::All => 1..=year.days.len(),
+            DaySpec::Range(range) => range.clone(),
+        }
+    }
+
+    pub fn is_single(&self) -> bool {
+        matches!(self, DaySpec::Range(range) if range.start() == range.end())
+    }
+
+    /// Checks that every day in the selection is registered for `year`.
+    pub fn validate(&self, year: &Year) -> Result<(), AocError> {
+        if let DaySpec::Range(range) = self {
+            year.solution(*range.start())?;
+            year.solution(*range.end())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DaySpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |d: &str| {
+            d.trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid day '{}'", d))
+        };
+
+        if s == "all" {
+            Ok(Self::All)
+        } else if let Some((from, to)) = s.split_once("..") {
+            let from = parse_day(from)?;
+            let to = match to.strip_prefix('=') {
+                Some(to) => parse_day(to)?,
+                None => parse_day(to)?
+                    .checked_sub(1)
+                    .ok_or_else(|| format!("Empty day range '{}'", s))?,
+            };
+            if from > to {
+                return Err(format!("Empty day range '{}'", s));
+            }
+            Ok(Self::Range(from..=to))
+        } else {
+            let day = parse_day(s)?;
+            Ok(Self::Range(day..=day))
+        }
+    }
+}
+
+/// Output format for run results.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// Which parts of a day to run.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Parts {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Parts {
+    pub fn includes(self, part: usize) -> bool {
+        matches!(
+            (self, part),
+            (Parts::One, 1) | (Parts::Two, 2) | (Parts::Both, _)
+        )
+    }
+}
+
+/// How a part's run ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// The day's time limit ran out before the part finished.
+    Timeout,
+    /// The solver panicked before the part finished.
+    Panicked,
+    /// The part is a stub, so it wasn't run.
+    NotImplemented,
+    /// The day couldn't be run, such as when its input is missing.
+    Failed,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Status::Ok => "ok",
+            Status::Timeout => "timeout",
+            Status::Panicked => "panicked",
+            Status::NotImplemented => "not implemented",
+            Status::Failed => "failed",
+        })
+    }
+}
+
+pub struct PartResult {
+    pub status: Status,
+    /// The part's answer, `None` unless the status is [`Status::Ok`].
+    pub answer: Option<Answer>,
+    pub elapsed: Duration,
+}
+
+pub struct DayResult {
+    pub year: u16,
+    pub day: usize,
+    pub part1: Option<PartResult>,
+    pub part2: Option<PartResult>,
+    /// Time spent reading the input, excluded from the parse and part timings.
+    pub read: Duration,
+    pub parse: Duration,
+    pub elapsed: Duration,
+    /// Heap usage of the whole day, when built with the `alloc-stats` feature and the day
+    /// ran to completion.
+    pub memory: Option<MemoryStats>,
+    /// Why the day couldn't be run, when its parts are [`Status::Failed`].
+    pub error: Option<AocError>,
+}
+
+impl DayResult {
+    /// The parts that were run, along with their part number.
+    pub fn parts(&self) -> impl Iterator<Item = (usize, &PartResult)> {
+        [(1, &self.part1), (2, &self.part2)]
+            .into_iter()
+            .filter_map(|(part, result)| Some((part, result.as_ref()?)))
+    }
+
+    /// A part's answer on a single line. Pictures are printed below the results instead.
+    fn answer(part: &Option<PartResult>) -> String {
+        match part {
+            None => "-".to_string(),
+            Some(p) => match (&p.answer, p.status) {
+                (Some(answer), _) if answer.is_picture() => "see below".to_string(),
+                (Some(answer), _) => answer.to_string(),
+                (None, status) => status.to_string().to_uppercase(),
+            },
+        }
+    }
+}
+
+/// Reads a day's input from `input`, a file path or `-` for stdin, defaulting to the
+/// year's `dayNN.txt` in the inputs directory.
+pub fn read_input(year: &Year, day: usize, input: Option<&str>) -> Result<Input, AocError> {
+    match input {
+        Some("-") => Input::from_stdin(),
+        Some(path) => Input::from_file(Path::new(path)),
+        None => Input::from_file(&aoc::find_input(&year.input_path(day))?),
+    }
+}
+
+/// Progress reported by the worker thread running a day.
+enum Stage {
+    Failed(AocError),
+    Read(Duration),
+    Parsed(Duration),
+    Part(usize, PartResult),
+    Memory(MemoryStats),
+}
+
+/// Runs a day on a worker thread. If `timeout` runs out first, the parts that haven't
+/// finished are reported as [`Status::Timeout`] and the worker is left running in the
+/// background, as threads can't be stopped from the outside.
+pub fn run_day(
+    year: &'static Year,
+    day: usize,
+    input: Option<&str>,
+    parts: Parts,
+    timeout: Option<Duration>,
+) -> Result<DayResult, AocError> {
+    let solution = year.solution(day)?;
+    // Stubs aren't run, their answer would be made up
+    let stubs = solution
+        .metadata()
+        .parts
+        .map(|status| status == PartStatus::Stub);
+    let runs = move |part: usize| parts.includes(part) && !stubs[part - 1];
+    let input = input.map(|i| i.to_string());
+    let (sender, receiver) = mpsc::channel();
+
+    let now = Instant::now();
+    thread::Builder::new()
+        .name(format!("day{}", day))
+        .spawn(move || {
+            memory::reset();
+            let input = match read_input(year, day, input.as_deref()) {
+                Ok(input) => input,
+                Err(e) => {
+                    let _ = sender.send(Stage::Failed(e));
+                    return;
+                }
+            };
+            let _ = sender.send(Stage::Read(now.elapsed()));
+
+            let parse_start = Instant::now();
+            let input = solution.parse(&input);
+            let _ = sender.send(Stage::Parsed(parse_start.elapsed()));
+
+            for part in [1, 2].into_iter().filter(|p| runs(*p)) {
+                let result = run_part(|| match part {
+                    1 => solution.part1(input.as_ref()),
+                    _ => solution.part2(input.as_ref()),
+                });
+                let _ = sender.send(Stage::Part(part, result));
+            }
+
+            if let Some(stats) = memory::stats() {
+                let _ = sender.send(Stage::Memory(stats));
+            }
+        })
+        .expect("Failed to spawn day thread");
+
+    let deadline = timeout.map(|timeout| now + timeout);
+    let mut result = DayResult {
+        year: year.year,
+        day,
+        part1: None,
+        part2: None,
+        read: Duration::ZERO,
+        parse: Duration::ZERO,
+        elapsed: Duration::ZERO,
+        memory: None,
+        error: None,
+    };
+
+    // The worker hangs up once it's done, or when it panics
+    let unfinished = loop {
+        let stage = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match stage {
+            Ok(Stage::Failed(e)) => return Err(e),
+            Ok(Stage::Read(read)) => result.read = read,
+            Ok(Stage::Parsed(parse)) => result.parse = parse,
+            Ok(Stage::Part(1, part)) => result.part1 = Some(part),
+            Ok(Stage::Part(_, part)) => result.part2 = Some(part),
+            Ok(Stage::Memory(stats)) => result.memory = Some(stats),
+            Err(RecvTimeoutError::Timeout) => break Status::Timeout,
+            Err(RecvTimeoutError::Disconnected) => break Status::Panicked,
+        }
+    };
+    result.elapsed = now.elapsed();
+
+    for (part, slot) in [(1, &mut result.part1), (2, &mut result.part2)] {
+        if parts.includes(part) && slot.is_none() {
+            *slot = Some(PartResult {
+                status: if runs(part) {
+                    unfinished
+                } else {
+                    Status::NotImplemented
+                },
+                answer: None,
+                elapsed: Duration::ZERO,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Runs `days` on a pool of `jobs` threads, returning the results in the order of `days`.
+/// Each day still times itself on its own worker, so timings don't include time spent
+/// waiting for a free thread. A day that can't be run, such as when its input is missing,
+/// is reported as [`Status::Failed`] along with its error, and the other days carry on.
+pub fn run_days(
+    year: &'static Year,
+    days: &[usize],
+    input: Option<&str>,
+    parts: Parts,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Vec<DayResult> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<DayResult, AocError>>>> =
+        Mutex::new(days.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+                let result = run_day(year, day, input, parts, timeout);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .zip(days)
+        .map(|(result, &day)| match result.expect("Every day is run") {
+            Ok(result) => result,
+            Err(e) => {
+                let failed = || PartResult {
+                    status: Status::Failed,
+                    answer: None,
+                    elapsed: Duration::ZERO,
+                };
+                DayResult {
+                    year: year.year,
+                    day,
+                    part1: parts.includes(1).then(failed),
+                    part2: parts.includes(2).then(failed),
+                    read: Duration::ZERO,
+                    parse: Duration::ZERO,
+                    elapsed: Duration::ZERO,
+                    memory: None,
+                    error: Some(e),
+                }
+            }
+        })
+        .collect()
+}
+
+fn run_part(part: impl FnOnce() -> Answer) -> PartResult {
+    let now = Instant::now();
+    let answer = part();
+    let elapsed = now.elapsed();
+
+    match answer {
+        Answer::NotImplemented => PartResult {
+            status: Status::NotImplemented,
+            answer: None,
+            elapsed,
+        },
+        answer => PartResult {
+            status: Status::Ok,
+            answer: Some(answer),
+            elapsed,
+        },
+    }
+}
+
+pub fn print_results(results: &[DayResult], format: Format) {
+    match format {
+        Format::Text => {
+            if let [result] = results {
+                println!(
+                    "Completed in {:.2?} (read {:.2?}, parse {:.2?}, part 1 {}, part 2 {}{}). Result: {:?}",
+                    result.elapsed,
+                    result.read,
+                    result.parse,
+                    part_time(&result.part1),
+                    part_time(&result.part2),
+                    result.memory.as_ref().map_or(String::new(), |m| format!(
+                        ", peak heap {}, {} allocations",
+                        memory::format_bytes(m.peak),
+                        m.allocations
+                    )),
+                    (
+                        DayResult::answer(&result.part1),
+                        DayResult::answer(&result.part2)
+                    )
+                );
+            } else {
+                print_table(results);
+            }
+            print_pictures(results);
+        }
+        Format::Json => print_json(results),
+        Format::Csv => print_csv(results),
+    }
+}
+
+fn print_json(results: &[DayResult]) {
+    let records: Vec<_> = results
+        .iter()
+        .flat_map(|r| {
+            r.parts().map(move |(part, result)| {
+                serde_json::json!({
+                    "year": r.year,
+                    "day": r.day,
+                    "part": part,
+                    "answer": result.answer.as_ref().map(|a| a.to_string()),
+                    "duration_ns": result.elapsed.as_nanos() as u64,
+                    "status": result.status.to_string(),
+                    "peak_bytes": r.memory.as_ref().map(|m| m.peak),
+                    "allocations": r.memory.as_ref().map(|m| m.allocations),
+                })
+            })
+        })
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&records).unwrap());
+}
+
+/// Quotes a CSV field if it holds a comma, quote or newline, doubling any quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn print_csv(results: &[DayResult]) {
+    println!("year,day,part,answer,duration_ns,status,peak_bytes,allocations");
+    for r in results {
+        let (peak, allocations) = r
+            .memory
+            .as_ref()
+            .map_or((String::new(), String::new()), |m| {
+                (m.peak.to_string(), m.allocations.to_string())
+            });
+        for (part, result) in r.parts() {
+            println!(
+                "{},{},{},{},{},{},{},{}",
+                r.year,
+                r.day,
+                part,
+                csv_field(
+                    &result
+                        .answer
+                        .as_ref()
+                        .map_or(String::new(), |a| a.to_string())
+                ),
+                result.elapsed.as_nanos(),
+                result.status,
+                peak,
+                allocations
+            );
+        }
+    }
+}
+
+/// Prints the picture answers in full, which the results only point to.
+fn print_pictures(results: &[DayResult]) {
+    for r in results {
+        for (part, result) in r.parts() {
+            if let Some(answer @ Answer::Picture(_)) = &result.answer {
+                println!();
+                println!("Day {} part {}:", r.day, part);
+                println!("{}", answer);
+            }
+        }
+    }
+}
+
+fn part_time(part: &Option<PartResult>) -> String {
+    match part {
+        Some(p) if p.status == Status::Ok => format!("{:.2?}", p.elapsed),
+        _ => "-".to_string(),
+    }
+}
+
+fn print_table(results: &[DayResult]) {
+    let mut headers = vec![
+        "Day",
+        "Part 1",
+        "Part 2",
+        "Read",
+        "Parse",
+        "Part 1 time",
+        "Part 2 time",
+        "Time",
+    ];
+    // Heap columns are only shown when built with the `alloc-stats` feature
+    let measured = results.iter().any(|r| r.memory.is_some());
+    if measured {
+        headers.extend(["Peak heap", "Allocs"]);
+    }
+    // Answers are left aligned, everything else right aligned
+    let left_aligned = [
+        false, true, true, false, false, false, false, false, false, false,
+    ];
+
+    let mut rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|r| {
+            let mut row = vec![
+                r.day.to_string(),
+                DayResult::answer(&r.part1),
+                DayResult::answer(&r.part2),
+                format!("{:.2?}", r.read),
+                format!("{:.2?}", r.parse),
+                part_time(&r.part1),
+                part_time(&r.part2),
+                format!("{:.2?}", r.elapsed),
+            ];
+            if measured {
+                row.extend(match &r.memory {
+                    Some(m) => [memory::format_bytes(m.peak), m.allocations.to_string()],
+                    None => ["-".to_string(), "-".to_string()],
+                });
+            }
+            row
+        })
+        .collect();
+
+    let total = |duration: fn(&DayResult) -> Duration| {
+        format!("{:.2?}", results.iter().map(duration).sum::<Duration>())
+    };
+    let part_total = |part: fn(&DayResult) -> &Option<PartResult>| {
+        let elapsed = results
+            .iter()
+            .filter_map(|r| part(r).as_ref())
+            .map(|p| p.elapsed);
+        format!("{:.2?}", elapsed.sum::<Duration>())
+    };
+    rows.push(vec![
+        "Total".to_string(),
+        String::new(),
+        String::new(),
+        total(|r| r.read),
+        total(|r| r.parse),
+        part_total(|r| &r.part1),
+        part_total(|r| &r.part2),
+        total(|r| r.elapsed),
+    ]);
+    if measured {
+        let measurements = || results.iter().filter_map(|r| r.memory.as_ref());
+        let total = rows.last_mut().unwrap();
+        total.push(memory::format_bytes(
+            measurements().map(|m| m.peak).max().unwrap_or(0),
+        ));
+        total.push(
+            measurements()
+                .map(|m| m.allocations)
+                .sum::<u64>()
+                .to_string(),
+        );
+    }
+
+    print_aligned(&headers, &left_aligned, &rows, true);
+}
+
+/// Prints `rows` as a table under `headers`, right aligning every column not marked in
+/// `left_aligned`. With `total`, the last row is set apart as a total.
+pub fn print_aligned(headers: &[&str], left_aligned: &[bool], rows: &[Vec<String>], total: bool) {
+    let mut widths: Vec<_> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |row: &[&str]| {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .zip(left_aligned)
+            .map(|((cell, width), left)| {
+                if *left {
+                    format!("{:<width$}", cell, width = *width)
+                } else {
+                    format!("{:>width$}", cell, width = *width)
+                }
+            })
+            .collect();
+        println!("{}", cells.join(" | ").trim_end());
+    };
+    let separator: Vec<_> = widths.iter().map(|w| "-".repeat(*w)).collect();
+
+    print_row(headers);
+    println!("{}", separator.join("-+-"));
+    for (i, row) in rows.iter().enumerate() {
+        if total && i == rows.len() - 1 {
+            println!("{}", separator.join("-+-"));
+        }
+        print_row(&row.iter().map(|c| c.as_str()).collect::<Vec<_>>());
+    }
+}
+
+#[cfg(test)]
+mod day_spec_tests {
+    use super::DaySpec;
+    use crate::years;
+
+    fn days(spec: &str) -> Vec<usize> {
+        let year = years::get(2022).unwrap();
+        spec.parse::<DaySpec>().unwrap().days(year).collect()
+    }
+
+    #[test]
+    fn test_parse_day_spec() {
+        assert_eq!(vec![5], days("5"));
+        assert_eq!((1..=10).collect::<Vec<_>>(), days("1..=10"));
+        assert_eq!(vec![3, 4, 5, 6], days("3..7"));
+
+        let year = years::get(2022).unwrap();
+        assert_eq!((1..=year.days.len()).collect::<Vec<_>>(), days("all"));
+
+        assert!("1..1".parse::<DaySpec>().is_err());
+        assert!("3..1".parse::<DaySpec>().is_err());
+        assert!("3..=1".parse::<DaySpec>().is_err());
+        assert!("one".parse::<DaySpec>().is_err());
+    }
+
+    #[test]
+    fn test_validate_day_spec() {
+        let year = years::get(2022).unwrap();
+        let validate = |spec: &str| spec.parse::<DaySpec>().unwrap().validate(year);
+
+        assert!(validate("1..=10").is_ok());
+        assert!(validate("all").is_ok());
+        assert!(validate("0").is_err());
+        assert!(validate("0..=3").is_err());
+        assert!(validate(&format!("1..={}", year.days.len() + 1)).is_err());
+    }
+}
+
+#[cfg(test)]
+mod csv_tests {
+    use super::csv_field;
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!("24000", csv_field("24000"));
+        assert_eq!("\"1,2\"", csv_field("1,2"));
+        assert_eq!("\"say \"\"hi\"\"\"", csv_field("say \"hi\""));
+        assert_eq!("\"#.\n.#\"", csv_field("#.\n.#"));
+    }
+}
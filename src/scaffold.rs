@@ -0,0 +1,187 @@
+use std::{fs, path::Path};
+
+use crate::{aoc, error::AocError, years::Year};
+
+const TEMPLATE: &str = include_str!("days/blank_day.txt");
+
+/// An example without expected answers, which the examples test reports as pending.
+const EXAMPLE_TEMPLATE: &str = "[[example]]
+# part1 = \"\"
+# part2 = \"\"
+input = '''
+'''
+";
+
+/// Creates `dayN.rs` in the year's module from the blank day template along with an
+/// empty `dayNN.txt` in the inputs directory and a `dayNN.toml` in the examples directory
+/// to paste the example into, and registers the new day in the module's `mod.rs`.
+pub fn new_day(year: &Year, day: usize) -> Result<(), AocError> {
+    if day != year.days.len() + 1 {
+        return Err(AocError::Scaffold(format!(
+            "day {} can't be added to {}, the next day is {}",
+            day,
+            year.year,
+            year.days.len() + 1
+        )));
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let module_dir = root.join("src").join(year.module);
+    let source_path = module_dir.join(format!("day{}.rs", day));
+    let input_path = aoc::manifest_input_dir().join(year.input_path(day));
+    let example_path = root
+        .join("examples")
+        .join(year.input_path(day).with_extension("toml"));
+    let mod_path = module_dir.join("mod.rs");
+
+    if source_path.exists() {
+        return Err(AocError::Scaffold(format!(
+            "{} already exists",
+            source_path.display()
+        )));
+    }
+
+    let mod_rs = fs::read_to_string(&mod_path).map_err(|e| AocError::Scaffold(e.to_string()))?;
+    let mod_rs = register_day(&mod_rs, day)?;
+
+    fs::write(&source_path, TEMPLATE.replace("{{day}}", &day.to_string()))
+        .map_err(|e| AocError::Scaffold(e.to_string()))?;
+    for (path, contents) in [(&input_path, ""), (&example_path, EXAMPLE_TEMPLATE)] {
+        if !path.exists() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| AocError::Scaffold(e.to_string()))?;
+            }
+            fs::write(path, contents).map_err(|e| AocError::Scaffold(e.to_string()))?;
+        }
+    }
+    fs::write(&mod_path, mod_rs).map_err(|e| AocError::Scaffold(e.to_string()))?;
+
+    crate::info!("Created {}", source_path.display());
+    crate::info!("Created {}", input_path.display());
+    crate::info!("Created {}", example_path.display());
+    crate::info!("Registered day {} in {}", day, mod_path.display());
+
+    Ok(())
+}
+
+/// Adds `mod dayN;` and the `ALL_DAYS` entry for `day` after the closest earlier day.
+fn register_day(mod_rs: &str, day: usize) -> Result<String, AocError> {
+    let mut lines: Vec<String> = mod_rs.lines().map(|l| l.to_string()).collect();
+
+    let mod_day = |line: &str| {
+        line.strip_prefix("pub mod day")?
+            .strip_suffix(';')?
+            .parse::<usize>()
+            .ok()
+    };
+    let entry_day = |line: &str| {
+        line.trim()
+            .strip_prefix("&day")?
+            .split("::")
+            .next()?
+            .parse::<usize>()
+            .ok()
+    };
+
+    let mod_index = lines
+        .iter()
+        .rposition(|l| mod_day(l).is_some_and(|d| d < day))
+        .map_or(0, |i| i + 1);
+    lines.insert(mod_index, format!("pub mod day{};", day));
+
+    // The first day of a year goes right after the opening of the registry
+    let entry_index = lines
+        .iter()
+        .rposition(|l| entry_day(l).is_some_and(|d| d < day))
+        .or_else(|| {
+            lines
+                .iter()
+                .position(|l| l.contains("ALL_DAYS") && l.trim_end().ends_with('['))
+        })
+        .ok_or_else(|| {
+            AocError::Scaffold("no `ALL_DAYS … = [` line to register the day in".to_string())
+        })?
+        + 1;
+    lines.insert(entry_index, format!("        &day{0}::Day{0}", day));
+
+    // Every entry but the last needs a trailing comma
+    let entries: Vec<_> = (0..lines.len())
+        .filter(|i| entry_day(&lines[*i]).is_some())
+        .collect();
+    for (n, i) in entries.iter().enumerate() {
+        let entry = lines[*i].trim_end_matches(',').to_string();
+        lines[*i] = if n + 1 < entries.len() {
+            entry + ","
+        } else {
+            entry
+        };
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod scaffold_tests {
+    use super::register_day;
+
+    #[test]
+    fn test_register_day() {
+        let mod_rs = r"pub mod day1;
+pub mod day10;
+pub mod day2;
+
+counted_array!(
+    pub const ALL_DAYS: [&dyn AnySolution; _] = [
+        &day1::Day1,
+        &day2::Day2
+    ]
+);
+";
+
+        assert_eq!(
+            r"pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+
+counted_array!(
+    pub const ALL_DAYS: [&dyn AnySolution; _] = [
+        &day1::Day1,
+        &day2::Day2,
+        &day3::Day3
+    ]
+);
+",
+            register_day(mod_rs, 3).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_register_first_day() {
+        let mod_rs = r"use counted_array::counted_array;
+
+use crate::aoc::AnySolution;
+
+counted_array!(
+    pub const ALL_DAYS: [&dyn AnySolution; _] = [
+    ]
+);
+";
+
+        assert_eq!(
+            r"pub mod day1;
+use counted_array::counted_array;
+
+use crate::aoc::AnySolution;
+
+counted_array!(
+    pub const ALL_DAYS: [&dyn AnySolution; _] = [
+        &day1::Day1
+    ]
+);
+",
+            register_day(mod_rs, 1).unwrap()
+        );
+        assert!(register_day("pub mod day1;\n", 2).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    aoc,
+    error::AocError,
+    runner::{self, Format, Parts},
+    years::Year,
+};
+
+/// Re-runs a day every time its input file changes, polling the file's modification
+/// time and size every `interval`. Runs until interrupted.
+pub fn watch(
+    year: &'static Year,
+    day: usize,
+    input: Option<&str>,
+    interval: Duration,
+    timeout: Option<Duration>,
+) -> Result<(), AocError> {
+    year.solution(day)?;
+    let path = match input {
+        Some(path) => PathBuf::from(path),
+        None => aoc::find_input(&year.input_path(day))?,
+    };
+    let path_str = path.to_string_lossy();
+
+    let mut last_seen = None;
+    loop {
+        let current = file_stamp(&path);
+        if current != last_seen {
+            last_seen = current;
+
+            // A solver panicking on a half-written input is reported as PANICKED by
+            // the runner, so it doesn't end the watch
+            match runner::run_day(year, day, Some(&path_str), Parts::Both, timeout) {
+                Ok(result) => runner::print_results(&[result], Format::Text),
+                Err(e) => eprintln!("error: {}", e),
+            }
+            crate::info!("Watching {} for changes...", path.display());
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
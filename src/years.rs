@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use crate::{aoc::AnySolution, days, error::AocError};
+
+/// An event year and its registered days. Each year keeps its solutions in its
+/// own module under `src/`, with an `ALL_DAYS` registry in its `mod.rs`.
+pub struct Year {
+    pub year: u16,
+    /// Module under `src/` holding the year's solutions.
+    pub module: &'static str,
+    /// Directory holding the year's `dayNN.txt` inputs, relative to the inputs directory.
+    pub input_dir: &'static str,
+    pub days: &'static [&'static dyn AnySolution],
+}
+
+impl Year {
+    pub fn solution(&self, day: usize) -> Result<&'static dyn AnySolution, AocError> {
+        match day.checked_sub(1).and_then(|i| self.days.get(i)) {
+            Some(solution) => Ok(*solution),
+            None => Err(AocError::InvalidDay {
+                year: self.year,
+                day,
+                max: self.days.len(),
+            }),
+        }
+    }
+
+    /// Path of a day's input relative to the inputs directory, e.g. `2022/day01.txt`.
+    pub fn input_path(&self, day: usize) -> PathBuf {
+        PathBuf::from(self.input_dir).join(format!("day{:02}.txt", day))
+    }
+}
+
+pub const ALL_YEARS: [Year; 1] = [Year {
+    year: 2022,
+    module: "days",
+    input_dir: "2022",
+    days: &days::ALL_DAYS,
+}];
+
+pub const LATEST_YEAR: u16 = 2022;
+
+pub fn get(year: u16) -> Result<&'static Year, AocError> {
+    ALL_YEARS
+        .iter()
+        .find(|y| y.year == year)
+        .ok_or(AocError::InvalidYear(year))
+}
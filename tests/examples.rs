@@ -0,0 +1,132 @@
+//! Runs every registered day against the examples in `examples/YEAR/dayNN.toml`. Each file
+//! holds `[[example]]` tables with an `input` and the expected `part1`/`part2` answers.
+//! Days without an examples file and parts without an expected answer are listed as
+//! pending rather than failing, run with `--nocapture` to see them.
+
+use std::{
+    any::Any,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use aoc2022::{aoc::Input, years};
+
+struct Example {
+    input: String,
+    answers: [Option<String>; 2],
+}
+
+fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples")
+}
+
+/// Reads the examples in `path`, or `None` if there's no such file.
+fn load_examples(path: &Path) -> Option<Vec<Example>> {
+    let contents = fs::read_to_string(path).ok()?;
+    let table: toml::Table = contents
+        .parse()
+        .unwrap_or_else(|e| panic!("Failed to parse {}: {}", path.display(), e));
+
+    let examples = table
+        .get("example")
+        .and_then(|e| e.as_array())
+        .map_or(&[][..], |e| e.as_slice());
+    let string = |example: &toml::Value, key: &str| {
+        example.get(key).map(|value| {
+            value
+                .as_str()
+                .unwrap_or_else(|| panic!("{} in {} must be a string", key, path.display()))
+                .to_string()
+        })
+    };
+
+    Some(
+        examples
+            .iter()
+            .map(|example| Example {
+                input: string(example, "input").unwrap_or_default(),
+                answers: [string(example, "part1"), string(example, "part2")],
+            })
+            .collect(),
+    )
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked".to_string())
+}
+
+#[test]
+fn examples_give_expected_answers() {
+    let mut pending = Vec::new();
+    let mut failures = Vec::new();
+
+    for year in &years::ALL_YEARS {
+        for day in 1..=year.days.len() {
+            let solution = year.solution(day).unwrap();
+            let path = examples_dir().join(year.input_path(day).with_extension("toml"));
+
+            let examples = match load_examples(&path) {
+                Some(examples) if !examples.is_empty() => examples,
+                _ => {
+                    pending.push(format!("{} day {}: no examples", year.year, day));
+                    continue;
+                }
+            };
+
+            for (i, example) in examples.iter().enumerate() {
+                let name = format!("{} day {} example {}", year.year, day, i + 1);
+                if example.answers.iter().all(Option::is_none) {
+                    pending.push(format!("{}: no expected answers", name));
+                    continue;
+                }
+
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    let input = solution.parse(&Input::new(example.input.as_str()));
+                    [
+                        solution.part1(input.as_ref()).to_string(),
+                        solution.part2(input.as_ref()).to_string(),
+                    ]
+                }));
+                // Parts without an expected answer still run, as they share the parsed input
+                let answers = match result {
+                    Ok(answers) => answers,
+                    Err(payload) => {
+                        failures.push(format!("{}: {}", name, panic_message(payload)));
+                        continue;
+                    }
+                };
+
+                for (part, (expected, answer)) in example.answers.iter().zip(answers).enumerate() {
+                    match expected {
+                        None => pending.push(format!("{} part {}", name, part + 1)),
+                        Some(expected) if *expected != answer => failures.push(format!(
+                            "{} part {}: expected {:?}, got {:?}",
+                            name,
+                            part + 1,
+                            expected,
+                            answer
+                        )),
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    if !pending.is_empty() {
+        println!("Pending examples:");
+        for example in &pending {
+            println!("  {}", example);
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "Examples failed:\n  {}",
+        failures.join("\n  ")
+    );
+}
@@ -0,0 +1,45 @@
+//! Every registered day can generate inputs, which must be the same for the same seed and
+//! accepted by the day's parser. Days fresh from `new-day`, with both parts still stubs,
+//! don't need a generator yet.
+
+use aoc2022::{
+    aoc::{Input, PartStatus},
+    years,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn generated_inputs_are_deterministic_and_parse() {
+    for year in &years::ALL_YEARS {
+        for day in 1..=year.days.len() {
+            let solution = year.solution(day).unwrap();
+            if solution.metadata().parts == [PartStatus::Stub; 2] {
+                continue;
+            }
+
+            let generate = |seed| {
+                solution
+                    .generate(&mut StdRng::seed_from_u64(seed), 20)
+                    .unwrap_or_else(|| panic!("{} day {} has no generator", year.year, day))
+            };
+
+            let input = generate(1);
+            assert_eq!(
+                input,
+                generate(1),
+                "{} day {} isn't deterministic",
+                year.year,
+                day
+            );
+            assert_ne!(
+                input,
+                generate(2),
+                "{} day {} ignores the seed",
+                year.year,
+                day
+            );
+
+            solution.parse(&Input::new(input));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use aoc2022::{
+    aoc::{Answer, Input, PartStatus, Solution},
+    days::{self, day1::Day1},
+    years,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn registry_lists_every_day() {
+    let year = years::get(2022).unwrap();
+
+    assert_eq!(days::ALL_DAYS.len(), year.days.len());
+    assert!(year.solution(1).is_ok());
+    assert!(year.solution(year.days.len() + 1).is_err());
+}
+
+#[test]
+fn day_is_usable_from_outside_the_crate() {
+    let input = Input::new(
+        r"1000
+2000
+
+4000",
+    );
+
+    let calories = Day1.parse(&input);
+    assert_eq!(Answer::Integer(4000), Day1.part1(&calories));
+    assert_eq!(Answer::Integer(7000), Day1.part2(&calories));
+}
+
+#[test]
+fn every_day_has_a_title() {
+    for year in &years::ALL_YEARS {
+        for (i, solution) in year.days.iter().enumerate() {
+            assert!(
+                !solution.metadata().title.is_empty(),
+                "{} day {} has no title",
+                year.year,
+                i + 1
+            );
+        }
+    }
+}
+
+#[test]
+fn stub_parts_are_not_implemented() {
+    for year in &years::ALL_YEARS {
+        for (i, solution) in year.days.iter().enumerate() {
+            let statuses = solution.metadata().parts;
+            if !statuses.contains(&PartStatus::Stub) {
+                continue;
+            }
+
+            // Only days with both parts stubbed may lack a generator
+            let input = solution
+                .generate(&mut StdRng::seed_from_u64(0), 10)
+                .unwrap_or_default();
+            let parsed = solution.parse(&Input::new(input));
+
+            for (part, status) in statuses.iter().enumerate() {
+                if *status != PartStatus::Stub {
+                    continue;
+                }
+                let answer = match part {
+                    0 => solution.part1(&*parsed),
+                    _ => solution.part2(&*parsed),
+                };
+                assert_eq!(
+                    Answer::NotImplemented,
+                    answer,
+                    "{} day {} part {} is a stub but has an answer",
+                    year.year,
+                    i + 1,
+                    part + 1
+                );
+            }
+        }
+    }
+}